@@ -0,0 +1,196 @@
+//! Top-level event loop: owns the view route stack and drives it against the
+//! terminal set up by `TerminalGuard`.
+
+use std::path::PathBuf;
+use std::time::Duration;
+
+use anyhow::Result;
+use crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use ratatui::widgets::{Block, Borders, Paragraph};
+
+use crate::config::Config;
+use crate::install::{PhaseStatus, PhaseTimeline, RetryChoice};
+use crate::logger::log_event;
+use crate::session_memory::SessionMemory;
+use crate::tui::views::{centered_rect, error::ErrorView, main::Main, render_view, Msg, Operation, View};
+use crate::tui::{Tui, TerminalGuard};
+
+/// Runs the whole installer. The `TerminalGuard` restores the terminal on
+/// drop no matter how `guide` returns, so there's no separate teardown call
+/// to remember here. `preselect_device` comes from the `--device` CLI flag.
+pub fn run(preselect_device: Option<PathBuf>) -> Result<()> {
+    let mut terminal = TerminalGuard::new()?;
+    // Surface the detected mode in the log up front — if the rest of the
+    // session behaves oddly (partitioning missing from the menu), this is
+    // the first thing to check.
+    log_event("startup", &[("chroot", &crate::chroot::is_chroot().to_string())]);
+    let result = guide(&mut terminal, preselect_device);
+    if result.is_err() {
+        // Leave the installer's output on screen so the error that follows
+        // (printed by `main` after this guard drops) isn't the only thing
+        // visible on an otherwise-cleared terminal.
+        terminal.set_clear_on_exit(false);
+    }
+    result
+}
+
+fn guide(terminal: &mut Tui, preselect_device: Option<PathBuf>) -> Result<()> {
+    let mut config = Config::default();
+    let mut preselect_device = preselect_device;
+
+    // A `--device` flag on the command line always wins over restoring a
+    // remembered session, since it's a deliberate choice made for this run.
+    if preselect_device.is_none() {
+        if let Some(memory) = SessionMemory::load() {
+            if prompt_restore_session(terminal, &memory)? {
+                config = memory.config;
+                preselect_device = memory.disk_path;
+            }
+        }
+    }
+
+    let mut stack: Vec<Box<dyn View>> = vec![Box::new(Main::new_with_device(&config, preselect_device.clone()))];
+
+    // Snapshot compared against on every tick so a crash mid-session doesn't
+    // lose everything: `SessionMemory` previously only saved once, on a
+    // clean exit past the end of this loop. Comparing against the last
+    // write keeps this cheap — most events (cursor movement, a keystroke
+    // that doesn't touch `Config`) don't trigger a write at all.
+    let mut last_saved = (config.clone(), preselect_device.clone());
+
+    loop {
+        let Some(view) = stack.last_mut() else {
+            break;
+        };
+
+        terminal.draw(|frame| render_view(view.as_mut(), frame, &config))?;
+
+        let event = event::read()?;
+        let Some(view) = stack.last_mut() else {
+            break;
+        };
+
+        // A view's `on_event` failing (a command errored, a data fetch
+        // failed) is usually recoverable — push an `ErrorView` instead of
+        // propagating and tearing the whole installer down. The one thing
+        // that still exits hard is a fault in `guide` itself (terminal I/O
+        // above via `?`), which no view can trigger.
+        match view.on_event(event, &mut config) {
+            Ok(Some(Msg::Push(next))) => stack.push(next),
+            Ok(Some(Msg::Pop)) => {
+                stack.pop();
+            }
+            Ok(Some(Msg::Close(Operation::Quit))) => break,
+            Ok(None) => {}
+            Err(err) => {
+                log::error!("{err:#}");
+                stack.push(Box::new(ErrorView::new(&err)));
+            }
+        }
+
+        let current = (config.clone(), preselect_device.clone());
+        if current != last_saved {
+            // Best-effort, same as the final save below: a failed write to
+            // the recovery file isn't worth interrupting the session over.
+            let _ = SessionMemory { config: current.0.clone(), disk_path: current.1.clone() }.save();
+            last_saved = current;
+        }
+    }
+
+    // Best-effort: remembering the session for next time is a convenience,
+    // not something worth surfacing an error for on the way out.
+    let _ = SessionMemory { config, disk_path: preselect_device }.save();
+
+    Ok(())
+}
+
+/// Blocking yes/no prompt shown once at startup when `SessionMemory::load`
+/// finds a previous run to offer restoring. Deliberately outside the normal
+/// `View` stack: it's a one-off decision made before `Main` even exists, not
+/// a navigable screen. Any key other than `y`/`Y` declines and starts fresh.
+fn prompt_restore_session(terminal: &mut Tui, memory: &SessionMemory) -> Result<bool> {
+    let device = memory.disk_path.as_ref().map(|p| p.display().to_string()).unwrap_or_else(|| "no disk".into());
+    let text = format!("Restore the previous session's settings ({device})? [y/N]");
+
+    loop {
+        terminal.draw(|frame| {
+            let area = centered_rect(50, 3, frame.size());
+            let block = Block::default().borders(Borders::ALL).title("Restore previous session?");
+            frame.render_widget(Paragraph::new(text.as_str()).block(block), area);
+        })?;
+
+        if let Event::Key(key) = event::read()? {
+            if key.kind != KeyEventKind::Press {
+                continue;
+            }
+            return Ok(matches!(key.code, KeyCode::Char('y') | KeyCode::Char('Y')));
+        }
+    }
+}
+
+/// Blocking modal shown when an install step's action fails: displays the
+/// wrapped error (stdout/stderr, via `CommandExt`'s error formatting) and
+/// waits for `r`/`s`/`a`. Outside the normal `View` stack for the same reason
+/// `prompt_restore_session` is: it's not a navigable screen, just a pause in
+/// whatever loop is driving the install.
+fn prompt_retry(terminal: &mut Tui, step_name: &str, error: &str) -> Result<RetryChoice> {
+    let title = format!("{step_name} failed — retry (r) / skip (s) / abort (a)");
+    loop {
+        terminal.draw(|frame| {
+            let area = centered_rect(70, 10, frame.size());
+            let block = Block::default().borders(Borders::ALL).title(title.as_str());
+            frame.render_widget(Paragraph::new(error).block(block), area);
+        })?;
+
+        if let Event::Key(key) = event::read()? {
+            if key.kind != KeyEventKind::Press {
+                continue;
+            }
+            if let KeyCode::Char(c) = key.code {
+                if let Some(choice) = RetryChoice::from_key(c) {
+                    return Ok(choice);
+                }
+            }
+        }
+    }
+}
+
+/// Runs one install step, retrying it through `prompt_retry` on failure.
+/// Every attempt (including a skip or abort) is recorded on `timeline` so
+/// the history view reflects what actually happened, not just the final
+/// outcome. Returns `Ok(false)` when the step was aborted — the caller
+/// should unwind the install rather than continue to the next step.
+///
+/// Not yet called anywhere: the install flow this backs (basestrap,
+/// bootloader install, ...) hasn't been built as a step-wise runner in this
+/// tree. This is the retry harness that runner will drive each step
+/// through.
+pub fn run_step_with_retry(
+    terminal: &mut Tui,
+    timeline: &mut PhaseTimeline,
+    name: &str,
+    mut action: impl FnMut() -> Result<()>,
+) -> Result<bool> {
+    loop {
+        let start = std::time::Instant::now();
+        match action() {
+            Ok(()) => {
+                timeline.record(name, PhaseStatus::Success, start.elapsed());
+                return Ok(true);
+            }
+            Err(err) => {
+                timeline.record(name, PhaseStatus::Failed, start.elapsed());
+                let choice = prompt_retry(terminal, name, &format!("{err:#}"))?;
+                log_event("install-step-retry-choice", &[("step", name), ("choice", &format!("{choice:?}"))]);
+                match choice {
+                    RetryChoice::Retry => continue,
+                    RetryChoice::Skip => {
+                        timeline.record(name, PhaseStatus::Skipped, Duration::ZERO);
+                        return Ok(true);
+                    }
+                    RetryChoice::Abort => return Ok(false),
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,331 @@
+//! Applying an in-memory partition plan to a real disk.
+
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use anyhow::{Context, Result};
+use gptman::{GPTPartitionEntry, GPT};
+use log::info;
+
+use crate::command::CommandExt;
+use crate::logger::log_dir;
+use crate::tui::data::partition::gpt::{type_guid_from_string, type_guid_to_string};
+use crate::tui::data::partition::{CompatDevice, Disk, FileSystem, FstabKeyMode, GptAttributes, MemPartition, MemTableEntry, TableType};
+
+/// Writes `disk`'s in-memory GPT partition plan to the real device via
+/// gptman, replacing every existing entry with `mem_table`'s planned layout.
+/// Only GPT has a real-write path here — this tool has no way to write MBR
+/// tables, so this refuses non-GPT disks (as `apply_device` already does
+/// before calling it).
+///
+/// Callers must already have called [`backup_partition_table`]: once
+/// `write_into` returns, the previous layout is gone beyond that backup.
+pub fn write_partition_table(disk: &Disk, mem_table: &[MemTableEntry]) -> Result<()> {
+    anyhow::ensure!(disk.table_type == TableType::Gpt, "writing a real partition table is only supported for GPT disks");
+
+    let mut file = std::fs::OpenOptions::new()
+        .read(true)
+        .write(true)
+        .open(&disk.path)
+        .with_context(|| format!("failed to open {} for writing", disk.path.display()))?;
+
+    let mut gpt = GPT::find_from(&mut file)
+        .or_else(|_| GPT::new_from(&mut file, disk.sector_size, fresh_guid()))
+        .with_context(|| format!("failed to read or initialize a GPT header on {}", disk.path.display()))?;
+
+    for number in 1..=gpt.header.number_of_partition_entries {
+        gpt[number] = GPTPartitionEntry {
+            partition_type_guid: [0; 16],
+            unique_partition_guid: [0; 16],
+            starting_lba: 0,
+            ending_lba: 0,
+            attribute_bits: 0,
+            partition_name: "".into(),
+        };
+    }
+
+    for entry in mem_table {
+        let MemTableEntry::Partition(partition) = entry else { continue };
+        gpt[partition.number] = GPTPartitionEntry {
+            partition_type_guid: type_guid_from_string(partition.filesystem.gpt_type_guid()),
+            unique_partition_guid: fresh_guid(),
+            starting_lba: partition.start,
+            ending_lba: partition.start + partition.sectors - 1,
+            attribute_bits: partition.gpt_attributes.to_bits(),
+            partition_name: partition.label.clone().unwrap_or_default().as_str().into(),
+        };
+    }
+
+    GPT::write_protective_mbr_into(&mut file, gpt.sector_size)
+        .with_context(|| format!("failed to write the protective MBR on {}", disk.path.display()))?;
+    gpt.write_into(&mut file)
+        .with_context(|| format!("failed to write the GPT table on {}", disk.path.display()))?;
+
+    info!("wrote partition table to {}", disk.path.display());
+    Ok(())
+}
+
+/// How long to wait after asking the kernel to re-read the partition table
+/// before trusting that the new partition device nodes (`/dev/sdaN`) are
+/// there. `blockdev --rereadpt` returns as soon as it issues the ioctl, not
+/// once udev has finished creating nodes for the result, so this is a
+/// pragmatic settle time rather than something either tool guarantees.
+const PARTITION_TABLE_SETTLE: Duration = Duration::from_millis(500);
+
+/// Tells the running kernel to re-read `device_path`'s partition table after
+/// [`write_partition_table`] has changed it on disk. Without this the kernel
+/// keeps serving its old view of the device: `format_partition`'s
+/// `mkfs.<fs>` would fail with "No such file or directory" on every
+/// partition the apply just created, since no device node exists for it yet.
+fn reread_partition_table(device_path: &Path) -> Result<()> {
+    Command::new("blockdev")
+        .arg("--rereadpt")
+        .arg(device_path)
+        .run()
+        .with_context(|| format!("failed to make the kernel re-read the partition table on {}", device_path.display()))?;
+
+    std::thread::sleep(PARTITION_TABLE_SETTLE);
+    Ok(())
+}
+
+static GUID_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+/// A GUID unique enough to avoid collisions between the disk/partition GUIDs
+/// `write_partition_table` mints in a single `apply` run — seeded from
+/// wall-clock time and a per-process counter, not cryptographically random.
+/// GPT only requires these to be unique, not unpredictable.
+fn fresh_guid() -> [u8; 16] {
+    let nanos = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_nanos();
+    let counter = GUID_COUNTER.fetch_add(1, Ordering::Relaxed);
+    let mut bytes = nanos.to_le_bytes();
+    for (byte, counter_byte) in bytes[8..].iter_mut().zip(counter.to_le_bytes()) {
+        *byte ^= counter_byte;
+    }
+    bytes
+}
+
+/// Dumps the device's existing partition table to a timestamped file in the
+/// log directory before we write anything new, so a botched apply can be
+/// recovered with `sgdisk --load-backup`. Keyed by device path so backups
+/// from different disks in the same session don't collide.
+///
+/// GPT and hybrid-MBR devices are covered by `sgdisk --backup`, which also
+/// captures the protective/hybrid MBR. This is best-effort: a device with no
+/// existing table has nothing to back up, so that case is not an error.
+pub fn backup_partition_table(device_path: &Path) -> Result<PathBuf> {
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    let file_name = format!(
+        "{}-{timestamp}.sgdisk-backup",
+        device_path.file_name().and_then(|n| n.to_str()).unwrap_or("disk")
+    );
+    let backup_path = log_dir().join(file_name);
+
+    Command::new("sgdisk")
+        .arg(format!("--backup={}", backup_path.display()))
+        .arg(device_path)
+        .run()
+        .with_context(|| format!("failed to back up partition table of {}", device_path.display()))?;
+
+    info!("backed up partition table of {} to {}", device_path.display(), backup_path.display());
+    Ok(backup_path)
+}
+
+/// The ground-truth fields `verify_applied` checks for one partition:
+/// everything `apply` intended to write, in the same shape a re-read of the
+/// table produces.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExpectedPartition {
+    pub number: u32,
+    pub starting_lba: u64,
+    pub ending_lba: u64,
+    pub type_guid: String,
+}
+
+impl ExpectedPartition {
+    fn from_mem_partition(partition: &MemPartition) -> Self {
+        Self {
+            number: partition.number,
+            starting_lba: partition.start,
+            ending_lba: partition.start + partition.sectors - 1,
+            type_guid: partition.filesystem.gpt_type_guid().to_string(),
+        }
+    }
+}
+
+/// A discrepancy between what `apply` intended to write and what a re-read
+/// of the table found for a given partition number. Either side can be
+/// `None`: `expected` only is a partition that silently failed to get
+/// written; `found` only is stray data `apply` didn't put there.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ApplyMismatch {
+    pub number: u32,
+    pub expected: Option<ExpectedPartition>,
+    pub found: Option<ExpectedPartition>,
+}
+
+/// Compares the partitions `apply` intended to write (`mem_table`) against
+/// the partitions a re-read of the table actually found. Pure and
+/// independent of `read_back_partitions` so it's testable without a real
+/// disk or GPT image.
+pub fn diff_applied_partitions(mem_table: &[MemTableEntry], found: &[ExpectedPartition]) -> Vec<ApplyMismatch> {
+    let expected: Vec<ExpectedPartition> = mem_table
+        .iter()
+        .filter_map(|entry| match entry {
+            MemTableEntry::Partition(partition) => Some(ExpectedPartition::from_mem_partition(partition)),
+            MemTableEntry::Free(_) => None,
+        })
+        .collect();
+
+    let mut mismatches = Vec::new();
+    for expected in &expected {
+        match found.iter().find(|found| found.number == expected.number) {
+            Some(found) if found == expected => {}
+            Some(found) => mismatches.push(ApplyMismatch {
+                number: expected.number,
+                expected: Some(expected.clone()),
+                found: Some(found.clone()),
+            }),
+            None => mismatches.push(ApplyMismatch { number: expected.number, expected: Some(expected.clone()), found: None }),
+        }
+    }
+    for found in found {
+        if !expected.iter().any(|expected| expected.number == found.number) {
+            mismatches.push(ApplyMismatch { number: found.number, expected: None, found: Some(found.clone()) });
+        }
+    }
+    mismatches
+}
+
+/// Re-reads `device_path`'s real GPT partition table right after a write, in
+/// the same shape `diff_applied_partitions` compares against.
+pub fn read_back_partitions(device_path: &Path) -> Result<Vec<ExpectedPartition>> {
+    let mut file = std::fs::File::open(device_path)
+        .with_context(|| format!("failed to open {} to verify the write", device_path.display()))?;
+    let gpt = GPT::find_from(&mut file)
+        .with_context(|| format!("failed to read back the GPT header on {}", device_path.display()))?;
+
+    Ok(gpt
+        .iter()
+        .filter(|(_, partition)| partition.is_used())
+        .map(|(number, partition)| ExpectedPartition {
+            number,
+            starting_lba: partition.starting_lba,
+            ending_lba: partition.ending_lba,
+            type_guid: type_guid_to_string(&partition.partition_type_guid),
+        })
+        .collect())
+}
+
+/// Verifies a write actually landed as intended: re-reads `device_path`'s
+/// table and compares it against `mem_table`, erroring out with every
+/// discrepancy found. Meant to run right after the real write and before
+/// mkfs touches anything — mkfs-ing a partition that didn't end up where we
+/// think it did would silently destroy whatever else was there.
+pub fn verify_applied(device_path: &Path, mem_table: &[MemTableEntry]) -> Result<()> {
+    let found = read_back_partitions(device_path)?;
+    let mismatches = diff_applied_partitions(mem_table, &found);
+    anyhow::ensure!(
+        mismatches.is_empty(),
+        "partition table on {} doesn't match what was written: {mismatches:?}",
+        device_path.display()
+    );
+    Ok(())
+}
+
+/// Runs the full apply pipeline for a GPT device: backs up the current
+/// table, writes the planned layout, verifies it landed, makes the kernel
+/// re-read the new table, formats every partition the plan newly created
+/// (not ones that were already `real` before this apply, so re-applying an
+/// already-applied plan doesn't wipe existing data), then writes `/etc/fstab`
+/// from the mountpoints set on the plan. Stops at the first failing step —
+/// the caller decides what to do with a partial failure, but the backup
+/// taken before the write survives regardless, recoverable with
+/// `sgdisk --load-backup`.
+///
+/// Only GPT is supported: this tool has no real-write path for MBR tables,
+/// so callers should show that limitation in the UI rather than letting it
+/// surface as this returning an error.
+pub fn apply_device(device: &CompatDevice, fstab_mode: FstabKeyMode) -> Result<()> {
+    anyhow::ensure!(
+        device.disk.table_type == TableType::Gpt,
+        "{} isn't a GPT disk — this build can only write GPT tables",
+        device.disk.path.display()
+    );
+
+    backup_partition_table(&device.disk.path)?;
+    write_partition_table(&device.disk, &device.mem_table)?;
+    verify_applied(&device.disk.path, &device.mem_table)?;
+    reread_partition_table(&device.disk.path)?;
+
+    for entry in &device.mem_table {
+        let MemTableEntry::Partition(partition) = entry else { continue };
+        if !partition.real && partition.filesystem != FileSystem::Unknown {
+            device.format_partition(partition.number)?;
+        }
+    }
+
+    std::fs::write("/etc/fstab", device.generate_fstab(fstab_mode)).context("failed to write /etc/fstab")?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mem_partition(number: u32, start: u64, sectors: u64, filesystem: FileSystem) -> MemTableEntry {
+        MemTableEntry::Partition(MemPartition {
+            number,
+            start,
+            sectors,
+            filesystem,
+            label: None,
+            mountpoint: None,
+            mkfs_options: None,
+            gpt_attributes: GptAttributes::default(),
+            real: true,
+        })
+    }
+
+    #[test]
+    fn matching_read_back_reports_no_mismatches() {
+        let mem_table = vec![mem_partition(1, 2048, 1000, FileSystem::Fat32)];
+        let found = vec![ExpectedPartition {
+            number: 1,
+            starting_lba: 2048,
+            ending_lba: 2048 + 1000 - 1,
+            type_guid: FileSystem::Fat32.gpt_type_guid().to_string(),
+        }];
+
+        assert!(diff_applied_partitions(&mem_table, &found).is_empty());
+    }
+
+    #[test]
+    fn a_shifted_start_is_reported_as_a_mismatch() {
+        let mem_table = vec![mem_partition(1, 2048, 1000, FileSystem::Ext4)];
+        let found = vec![ExpectedPartition {
+            number: 1,
+            starting_lba: 4096,
+            ending_lba: 4096 + 1000 - 1,
+            type_guid: FileSystem::Ext4.gpt_type_guid().to_string(),
+        }];
+
+        let mismatches = diff_applied_partitions(&mem_table, &found);
+        assert_eq!(mismatches.len(), 1);
+        assert_eq!(mismatches[0].number, 1);
+    }
+
+    #[test]
+    fn a_partition_missing_from_the_read_back_is_reported() {
+        let mem_table = vec![mem_partition(1, 2048, 1000, FileSystem::Ext4)];
+
+        let mismatches = diff_applied_partitions(&mem_table, &[]);
+        assert_eq!(mismatches.len(), 1);
+        assert!(mismatches[0].found.is_none());
+    }
+}
@@ -0,0 +1,23 @@
+//! Detects whether the installer is running inside an existing chroot (e.g.
+//! `arch-chroot`, used to repair or reconfigure an already-basestrapped
+//! system), so the main menu can skip partitioning/basestrap steps that
+//! don't make sense from inside one.
+
+/// Compares `/`'s device/inode to `/proc/1/root`'s. Under a normal boot
+/// they're the same directory (both are the real root); inside a chroot,
+/// `/` has been remounted to something else while PID 1 — running outside
+/// the chroot — still has the real root. This is the same check `ischroot`
+/// makes when `/proc` is mounted, without depending on that binary existing.
+pub fn is_chroot() -> bool {
+    match (std::fs::metadata("/"), std::fs::metadata("/proc/1/root")) {
+        (Ok(root), Ok(init_root)) => !same_inode(&root, &init_root),
+        // Either path being uninspectable never happens on a real Linux
+        // system; assume not chrooted rather than silently skipping steps.
+        _ => false,
+    }
+}
+
+fn same_inode(a: &std::fs::Metadata, b: &std::fs::Metadata) -> bool {
+    use std::os::unix::fs::MetadataExt;
+    a.dev() == b.dev() && a.ino() == b.ino()
+}
@@ -0,0 +1,140 @@
+//! Thin wrapper around `std::process::Command` used for every external
+//! command the installer shells out to (lsblk, sgdisk, mkfs, ...).
+
+use std::io::Read;
+use std::process::Command;
+use std::time::{Duration, Instant};
+
+use anyhow::{Context, Result};
+use thiserror::Error;
+
+use crate::logger::log_event;
+
+#[derive(Debug, Error)]
+pub enum CommandTimeoutError {
+    #[error("`{0}` did not finish within {1:?}")]
+    TimedOut(String, Duration),
+}
+
+pub trait CommandExt {
+    /// Runs the command, returning an error (with stdout/stderr attached) if
+    /// it exits non-zero.
+    fn run(&mut self) -> Result<()>;
+
+    /// Runs the command and returns its stdout, lossily converted to UTF-8
+    /// (invalid bytes become U+FFFD). Fine for output that's only ever
+    /// displayed to the user; for output that gets parsed strictly, prefer
+    /// `read_bytes` and let the parser (e.g. `serde_json`) report the exact
+    /// failure instead of silently mangling it.
+    fn read(&mut self) -> Result<String>;
+
+    /// Runs the command and returns its raw stdout bytes, unconverted. Use
+    /// this before feeding output into a strict parser, so corrupt or
+    /// non-UTF-8 output surfaces as a parse error instead of being masked by
+    /// lossy replacement.
+    fn read_bytes(&mut self) -> Result<Vec<u8>>;
+
+    /// Like `run`, but kills the child and returns `CommandTimeoutError` if it
+    /// hasn't exited within `timeout`. Use this for anything that touches the
+    /// network or a device that might hang (a bad mirror, a stuck mkfs).
+    fn run_timeout(&mut self, timeout: Duration) -> Result<()>;
+
+    /// Like `read`, but with the same timeout behavior as `run_timeout`.
+    fn read_timeout(&mut self, timeout: Duration) -> Result<String>;
+}
+
+impl CommandExt for Command {
+    fn run(&mut self) -> Result<()> {
+        let description = format!("{self:?}");
+        let output = self
+            .output()
+            .with_context(|| format!("failed to spawn `{description}`"))?;
+        wrap_command_error(self, &output)?;
+        log_event("command-executed", &[("command", &description)]);
+        Ok(())
+    }
+
+    fn read(&mut self) -> Result<String> {
+        Ok(String::from_utf8_lossy(&self.read_bytes()?).into_owned())
+    }
+
+    fn read_bytes(&mut self) -> Result<Vec<u8>> {
+        let description = format!("{self:?}");
+        let output = self
+            .output()
+            .with_context(|| format!("failed to spawn `{description}`"))?;
+        wrap_command_error(self, &output)?;
+        log_event("command-executed", &[("command", &description)]);
+        Ok(output.stdout)
+    }
+
+    fn run_timeout(&mut self, timeout: Duration) -> Result<()> {
+        read_timeout_inner(self, timeout)?;
+        Ok(())
+    }
+
+    fn read_timeout(&mut self, timeout: Duration) -> Result<String> {
+        Ok(String::from_utf8_lossy(&read_timeout_inner(self, timeout)?).into_owned())
+    }
+}
+
+fn read_timeout_inner(command: &mut Command, timeout: Duration) -> Result<Vec<u8>> {
+    let description = format!("{command:?}");
+    let mut child = command
+        .stdout(std::process::Stdio::piped())
+        .stderr(std::process::Stdio::piped())
+        .spawn()
+        .with_context(|| format!("failed to spawn `{description}`"))?;
+
+    // Drain stdout/stderr on their own threads instead of reading them after
+    // the poll loop: a chatty child fills the OS pipe buffer (~64KB) and
+    // blocks on write() long before `timeout` elapses, and the poll loop
+    // below only watches `try_wait`, so an undrained pipe would hang the
+    // child instead of letting it finish inside its budget.
+    let mut stdout_pipe = child.stdout.take().expect("stdout was piped");
+    let mut stderr_pipe = child.stderr.take().expect("stderr was piped");
+    let stdout_reader = std::thread::spawn(move || {
+        let mut buf = Vec::new();
+        let _ = stdout_pipe.read_to_end(&mut buf);
+        buf
+    });
+    let stderr_reader = std::thread::spawn(move || {
+        let mut buf = Vec::new();
+        let _ = stderr_pipe.read_to_end(&mut buf);
+        buf
+    });
+
+    let deadline = Instant::now() + timeout;
+    let status = loop {
+        if let Some(status) = child.try_wait()? {
+            break status;
+        }
+        if Instant::now() >= deadline {
+            let _ = child.kill();
+            let _ = child.wait();
+            let _ = stdout_reader.join();
+            let _ = stderr_reader.join();
+            return Err(CommandTimeoutError::TimedOut(description, timeout).into());
+        }
+        std::thread::sleep(Duration::from_millis(50));
+    };
+
+    let stdout = stdout_reader.join().unwrap_or_default();
+    let stderr = stderr_reader.join().unwrap_or_default();
+    let output = std::process::Output { status, stdout, stderr };
+    wrap_command_error(command, &output)?;
+    log_event("command-executed", &[("command", &description)]);
+    Ok(output.stdout)
+}
+
+fn wrap_command_error(command: &Command, output: &std::process::Output) -> Result<()> {
+    if output.status.success() {
+        return Ok(());
+    }
+    anyhow::bail!(
+        "`{command:?}` exited with {}\nstdout: {}\nstderr: {}",
+        output.status,
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
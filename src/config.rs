@@ -0,0 +1,38 @@
+//! The installer's accumulated configuration, built up view by view as the
+//! user makes choices and consumed by the install/apply flow at the end.
+
+use anyhow::{ensure, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::tui::data::partition::FstabKeyMode;
+use crate::tui::views::keyboard::resolve_keymap_path;
+
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Config {
+    pub keyboard_layout: Option<String>,
+    /// Console font written to `vconsole.conf`'s `FONT=` on install, e.g.
+    /// `ter-116n` for a non-Latin-friendly Terminus variant. `None` leaves
+    /// whatever font the kernel/initramfs already loaded untouched.
+    pub console_font: Option<String>,
+    pub timezone: Option<String>,
+    pub locale: Option<String>,
+    /// How `CompatDevice::generate_fstab` keys each entry's `<device>`
+    /// column. Defaults to `FstabKeyMode::Uuid`.
+    pub fstab_mode: FstabKeyMode,
+}
+
+impl Config {
+    /// Re-checks selections that could have gone stale between being picked
+    /// and the configure step actually consuming them (e.g. the environment
+    /// changed underneath a saved config, or a package was removed mid
+    /// session). Meant to run right before that step, so a missing keymap
+    /// fails with a message naming the layout instead of a raw `loadkeys`
+    /// error.
+    pub fn validate(&self) -> Result<()> {
+        if let Some(layout) = &self.keyboard_layout {
+            let found = resolve_keymap_path(layout)?;
+            ensure!(found.is_some(), "keyboard layout \"{layout}\" no longer has a matching keymap file");
+        }
+        Ok(())
+    }
+}
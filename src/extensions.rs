@@ -0,0 +1,132 @@
+//! Small extension traits shared across the TUI layer.
+
+/// Character- (not byte-) indexed string slicing, so windowing logic like
+/// `Input`'s horizontal scroll can pick `[start, end)` in terms of visible
+/// characters without risking a byte-index panic mid-UTF-8-sequence.
+pub trait StrExt {
+    /// Returns the substring spanning character indices `[start, end)`,
+    /// clamped to the string's length. `end < start` yields an empty string.
+    fn slice(&self, start: usize, end: usize) -> &str;
+
+    /// Case-insensitive subsequence fuzzy match: every character of
+    /// `pattern` must appear in `self`, in order, though not necessarily
+    /// contiguously. Returns `None` when it doesn't match at all, or
+    /// `Some((score, indices))` where `indices` are the matched character
+    /// positions in `self` and a higher `score` means a tighter, earlier
+    /// match — used to rank search results instead of leaving them in
+    /// whatever order they were searched in.
+    fn fuzzy_match(&self, pattern: &str) -> Option<(i64, Vec<usize>)> {
+        self.fuzzy_match_with_case(pattern, false)
+    }
+
+    /// Same algorithm as [`Self::fuzzy_match`], but compares characters
+    /// as-typed instead of folding case when `case_sensitive` is set — the
+    /// building block for `SearchableMenu`'s case-insensitive/smart-case
+    /// toggle.
+    fn fuzzy_match_with_case(&self, pattern: &str, case_sensitive: bool) -> Option<(i64, Vec<usize>)>;
+}
+
+impl StrExt for str {
+    fn slice(&self, start: usize, end: usize) -> &str {
+        let len = self.chars().count();
+        let start = start.min(len);
+        let end = end.min(len).max(start);
+        let byte_start = self.char_indices().nth(start).map_or(self.len(), |(i, _)| i);
+        let byte_end = self.char_indices().nth(end).map_or(self.len(), |(i, _)| i);
+        &self[byte_start..byte_end]
+    }
+
+    fn fuzzy_match_with_case(&self, pattern: &str, case_sensitive: bool) -> Option<(i64, Vec<usize>)> {
+        let choice: Vec<char> = self.chars().collect();
+        let (choice_folded, pattern_folded): (Vec<char>, Vec<char>) = if case_sensitive {
+            (choice.clone(), pattern.chars().collect())
+        } else {
+            (self.to_lowercase().chars().collect(), pattern.to_lowercase().chars().collect())
+        };
+
+        if pattern_folded.is_empty() {
+            return Some((0, Vec::new()));
+        }
+
+        let mut indices = Vec::with_capacity(pattern_folded.len());
+        let mut search_from = 0usize;
+        for &pc in &pattern_folded {
+            let pos = choice_folded[search_from..].iter().position(|&c| c == pc)? + search_from;
+            indices.push(pos);
+            search_from = pos + 1;
+        }
+
+        // Base credit per matched character, a bonus for runs of
+        // consecutive matches (rewards "adc" over "dxc" for pattern "dc"),
+        // a bonus for matching close to the start, and a penalty for
+        // leftover characters the pattern skipped over.
+        let mut score: i64 = 0;
+        for (i, &pos) in indices.iter().enumerate() {
+            score += 10;
+            if i > 0 && pos == indices[i - 1] + 1 {
+                score += 15;
+            }
+        }
+        score -= indices[0] as i64;
+        score -= (choice.len() as i64 - pattern_folded.len() as i64).max(0);
+
+        Some((score, indices))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn slice_picks_the_requested_character_range() {
+        assert_eq!("hello world".slice(0, 5), "hello");
+        assert_eq!("hello world".slice(6, 11), "world");
+    }
+
+    #[test]
+    fn slice_clamps_out_of_range_bounds() {
+        assert_eq!("abc".slice(1, 100), "bc");
+        assert_eq!("abc".slice(100, 200), "");
+    }
+
+    #[test]
+    fn slice_is_char_boundary_safe_on_multi_byte_text() {
+        assert_eq!("héllo".slice(0, 2), "hé");
+        assert_eq!("héllo".slice(1, 3), "él");
+    }
+
+    #[test]
+    fn fuzzy_match_ranks_a_tighter_match_higher() {
+        let adc = "adc".fuzzy_match("dc").unwrap();
+        let dxc = "dxc".fuzzy_match("dc").unwrap();
+        assert!(adc.0 > dxc.0, "expected adc ({}) > dxc ({})", adc.0, dxc.0);
+    }
+
+    #[test]
+    fn fuzzy_match_rejects_a_missing_character() {
+        assert_eq!("abc".fuzzy_match("dc"), None);
+    }
+
+    #[test]
+    fn fuzzy_match_is_case_insensitive() {
+        assert!("ADC".fuzzy_match("dc").is_some());
+    }
+
+    #[test]
+    fn fuzzy_match_returns_the_matched_indices() {
+        let (_, indices) = "adc".fuzzy_match("dc").unwrap();
+        assert_eq!(indices, vec![1, 2]);
+    }
+
+    #[test]
+    fn fuzzy_match_with_case_sensitive_true_requires_exact_case() {
+        assert_eq!("ADC".fuzzy_match_with_case("dc", true), None);
+        assert!("ADC".fuzzy_match_with_case("DC", true).is_some());
+    }
+
+    #[test]
+    fn fuzzy_match_with_case_insensitive_ignores_case() {
+        assert!("ADC".fuzzy_match_with_case("dc", false).is_some());
+    }
+}
@@ -0,0 +1,67 @@
+//! Small persisted "starred" list that floats favorite entries to the top of
+//! a menu (timezones, keyboard layouts, ...), keyed by the menu's own name so
+//! several menus can share one dotfile without colliding.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Favorites {
+    #[serde(flatten)]
+    by_menu: HashMap<String, Vec<String>>,
+}
+
+impl Favorites {
+    /// Loads the favorites file, falling back to an empty set if it's
+    /// missing or unreadable — favoriting is a convenience, not something
+    /// worth failing startup over.
+    pub fn load() -> Self {
+        std::fs::read_to_string(path())
+            .ok()
+            .and_then(|contents| serde_json::from_str(&contents).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self) -> Result<()> {
+        let path = path();
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    /// Stars or un-stars `item` in `menu`'s list.
+    pub fn toggle(&mut self, menu: &str, item: &str) {
+        let starred = self.by_menu.entry(menu.to_string()).or_default();
+        if let Some(index) = starred.iter().position(|i| i == item) {
+            starred.remove(index);
+        } else {
+            starred.push(item.to_string());
+        }
+    }
+
+    /// Reorders `items` so `menu`'s starred entries come first, in the order
+    /// they were starred, followed by the rest in their original order.
+    pub fn pin(&self, menu: &str, items: Vec<String>) -> Vec<String> {
+        let Some(starred) = self.by_menu.get(menu) else {
+            return items;
+        };
+        let (mut pinned, rest): (Vec<String>, Vec<String>) =
+            items.into_iter().partition(|item| starred.contains(item));
+        pinned.sort_by_key(|item| starred.iter().position(|s| s == item));
+        pinned.into_iter().chain(rest).collect()
+    }
+}
+
+/// `~/.config/artixide/favorites.json`, falling back to a temp directory if
+/// `HOME` isn't set (e.g. running as a stray process during early boot).
+fn path() -> PathBuf {
+    let base = std::env::var_os("HOME")
+        .map(PathBuf::from)
+        .unwrap_or_else(std::env::temp_dir);
+    base.join(".config/artixide/favorites.json")
+}
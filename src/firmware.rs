@@ -0,0 +1,33 @@
+//! Firmware detection used to pick boot-partition requirements.
+
+use std::path::Path;
+
+/// Whether we're booted under UEFI (as opposed to legacy BIOS). Checked by
+/// the presence of `/sys/firmware/efi`, which only exists under UEFI.
+pub fn is_uefi() -> bool {
+    Path::new("/sys/firmware/efi").exists()
+}
+
+/// Whether Secure Boot is enabled, read from whichever `SecureBoot-<guid>`
+/// file is present under `/sys/firmware/efi/efivars`. Each efivars file is
+/// the variable's 4-byte attribute flags followed by its payload; for
+/// `SecureBoot` that payload is a single byte, non-zero meaning enabled.
+///
+/// Returns `None` off UEFI (the question doesn't apply to BIOS boot) or if
+/// the variable can't be found or read — older or unusual UEFI firmware
+/// without Secure Boot support. Callers that warn about a bootloader/Secure
+/// Boot mismatch should treat `None` as "unknown", not "disabled".
+pub fn secure_boot_enabled() -> Option<bool> {
+    if !is_uefi() {
+        return None;
+    }
+    let entries = std::fs::read_dir("/sys/firmware/efi/efivars").ok()?;
+    for entry in entries.flatten() {
+        if !entry.file_name().to_string_lossy().starts_with("SecureBoot-") {
+            continue;
+        }
+        let bytes = std::fs::read(entry.path()).ok()?;
+        return bytes.get(4).map(|&payload| payload != 0);
+    }
+    None
+}
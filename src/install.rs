@@ -0,0 +1,111 @@
+//! Tracks the install flow's phases (partitioning, pacstrap, config,
+//! bootloader, ...) so the timeline view and the session log can show
+//! exactly what happened and how long it took, rather than just the current
+//! step's progress bar.
+
+use std::time::Duration;
+
+use crate::logger::log_event;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PhaseStatus {
+    Success,
+    Failed,
+    Skipped,
+}
+
+impl PhaseStatus {
+    pub fn label(self) -> &'static str {
+        match self {
+            PhaseStatus::Success => "success",
+            PhaseStatus::Failed => "failed",
+            PhaseStatus::Skipped => "skipped",
+        }
+    }
+}
+
+/// Outcome of a failed step, chosen interactively via the retry modal (see
+/// `app::prompt_retry`). Kept separate from `PhaseStatus` since it's a user
+/// decision made *about* a failure, not the recorded result of one — a
+/// retried step that then succeeds is still logged with `PhaseStatus::Success`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RetryChoice {
+    Retry,
+    Skip,
+    Abort,
+}
+
+impl RetryChoice {
+    /// Maps the single-key prompt (`r`/`s`/`a`, case-insensitive) shown by
+    /// the retry modal. `None` for any other key, so the caller keeps
+    /// waiting instead of guessing at an unrecognized keypress.
+    pub fn from_key(c: char) -> Option<Self> {
+        match c.to_ascii_lowercase() {
+            'r' => Some(RetryChoice::Retry),
+            's' => Some(RetryChoice::Skip),
+            'a' => Some(RetryChoice::Abort),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct PhaseRecord {
+    pub name: String,
+    pub status: PhaseStatus,
+    pub elapsed: Duration,
+}
+
+/// History of every phase the install flow has run through this session, in
+/// order. The progress bar shown during install only tracks the current
+/// step; this backs the "what already happened" timeline view.
+#[derive(Debug, Default, Clone)]
+pub struct PhaseTimeline {
+    pub records: Vec<PhaseRecord>,
+}
+
+impl PhaseTimeline {
+    /// Records a finished phase and writes it to the session log immediately,
+    /// so the history survives even if the install crashes before this
+    /// timeline is ever rendered.
+    pub fn record(&mut self, name: impl Into<String>, status: PhaseStatus, elapsed: Duration) {
+        let name = name.into();
+        log_event(
+            "phase-completed",
+            &[
+                ("phase", name.as_str()),
+                ("status", status.label()),
+                ("elapsed_ms", &elapsed.as_millis().to_string()),
+            ],
+        );
+        self.records.push(PhaseRecord { name, status, elapsed });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn records_append_in_order() {
+        let mut timeline = PhaseTimeline::default();
+        timeline.record("partitioning", PhaseStatus::Success, Duration::from_secs(3));
+        timeline.record("pacstrap", PhaseStatus::Failed, Duration::from_secs(12));
+
+        assert_eq!(timeline.records.len(), 2);
+        assert_eq!(timeline.records[0].name, "partitioning");
+        assert_eq!(timeline.records[1].status, PhaseStatus::Failed);
+    }
+
+    #[test]
+    fn retry_choice_from_key_is_case_insensitive() {
+        assert_eq!(RetryChoice::from_key('r'), Some(RetryChoice::Retry));
+        assert_eq!(RetryChoice::from_key('S'), Some(RetryChoice::Skip));
+        assert_eq!(RetryChoice::from_key('A'), Some(RetryChoice::Abort));
+    }
+
+    #[test]
+    fn retry_choice_from_key_rejects_unrecognized_keys() {
+        assert_eq!(RetryChoice::from_key('x'), None);
+    }
+}
@@ -0,0 +1,63 @@
+//! Log/recovery file locations, and session log initialization.
+
+use std::fs::File;
+use std::path::PathBuf;
+use std::sync::OnceLock;
+
+use anyhow::Result;
+
+/// Path chosen by `init_logger` for this session's log file, set once at
+/// startup and read back by `main`/the panic hook so the log location can be
+/// printed on exit. `OnceLock` rather than a plain `static mut` since it's
+/// written exactly once, early, and read from arbitrary later points
+/// (including the panic hook, which can't take a fallible path).
+static LOG_PATH: OnceLock<PathBuf> = OnceLock::new();
+
+/// Sets up the session's log output. Called first thing in `main`, before
+/// `tui::set_panic_hook` and `TerminalGuard::new` — a failure here shouldn't leave the
+/// terminal touched at all. Logs go to a file under `log_dir()` (named with
+/// the process ID so concurrent runs, e.g. two live-ISO shells, don't clobber
+/// each other) rather than stderr, since stderr is the alternate screen once
+/// the TUI starts.
+pub fn init_logger() -> Result<()> {
+    let path = log_dir().join(format!("artixide-{}.log", std::process::id()));
+    let file = File::create(&path)?;
+    env_logger::Builder::from_default_env()
+        .target(env_logger::Target::Pipe(Box::new(file)))
+        .init();
+    let _ = LOG_PATH.set(path);
+    Ok(())
+}
+
+/// The log file path chosen by `init_logger`, for printing to the user on
+/// exit (normal or panic) so a bug report knows where to look. `None` if
+/// `init_logger` hasn't run yet or failed before setting it.
+pub fn log_file_path() -> Option<PathBuf> {
+    LOG_PATH.get().cloned()
+}
+
+/// Logs a structured installer event: `event key1=val1 key2=val2 ...`, for
+/// actions worth reconstructing from the session log after the fact (a device
+/// selected, a partition created with its exact sectors, a filesystem chosen,
+/// a command executed). Plain `log::info!("{:#?}", x)` calls scattered
+/// through call sites don't give a post-install debugger anything greppable;
+/// this keeps the field order and format consistent.
+pub fn log_event(event: &str, fields: &[(&str, &str)]) {
+    let mut line = event.to_string();
+    for (key, value) in fields {
+        line.push_str(&format!(" {key}={value}"));
+    }
+    log::info!("{line}");
+}
+
+/// Directory backups, recovery files, and the session log live in. Prefers
+/// `/var/log/artixide` (writable on a live ISO running as root) and falls
+/// back to a temp directory otherwise.
+pub fn log_dir() -> PathBuf {
+    let preferred = PathBuf::from("/var/log/artixide");
+    if preferred.exists() || std::fs::create_dir_all(&preferred).is_ok() {
+        preferred
+    } else {
+        std::env::temp_dir().join("artixide")
+    }
+}
@@ -0,0 +1,63 @@
+//! Small macros used throughout the TUI layer to cut down on boilerplate.
+
+/// Match `$val` against `$pat`, evaluating to `$result` on success and panicking
+/// with a descriptive message otherwise. Used at call sites where an invariant
+/// (e.g. "the table has a selection whenever this branch runs") guarantees the
+/// pattern always matches, so an explicit panic is preferable to threading an
+/// `Option`/`Result` through code that can't actually fail.
+macro_rules! let_irrefutable {
+    ($val:expr, $pat:pat => $result:expr) => {
+        match $val {
+            $pat => $result,
+            _ => panic!(
+                "let_irrefutable!: `{}` did not match pattern `{}`",
+                stringify!($val),
+                stringify!($pat)
+            ),
+        }
+    };
+}
+
+/// Define a function returning a `&'static $ty`, lazily initialized on first
+/// access. Used for the layout constants shared by `render` methods so they
+/// aren't rebuilt on every frame.
+macro_rules! lazy {
+    (static $name:ident : $ty:ty = $init:expr;) => {
+        pub fn $name() -> &'static $ty {
+            static CELL: ::std::sync::OnceLock<$ty> = ::std::sync::OnceLock::new();
+            CELL.get_or_init(|| $init)
+        }
+    };
+}
+
+/// Generate a builder-style "args" struct for a widget constructor, with a
+/// `Default` impl and one chainable setter per field. Widgets like `Input` and
+/// `Menu` take one of these instead of a long constructor argument list.
+macro_rules! widget_args {
+    ($(#[$meta:meta])* $vis:vis struct $name:ident { $($field:ident : $ty:ty = $default:expr),* $(,)? }) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone)]
+        $vis struct $name {
+            $(pub $field: $ty,)*
+        }
+
+        impl Default for $name {
+            fn default() -> Self {
+                Self { $($field: $default,)* }
+            }
+        }
+
+        impl $name {
+            $(
+                pub fn $field(mut self, value: $ty) -> Self {
+                    self.$field = value;
+                    self
+                }
+            )*
+        }
+    };
+}
+
+pub(crate) use lazy;
+pub(crate) use let_irrefutable;
+pub(crate) use widget_args;
@@ -0,0 +1,51 @@
+mod app;
+mod apply;
+mod chroot;
+mod command;
+mod config;
+mod extensions;
+mod favorites;
+mod firmware;
+mod install;
+mod logger;
+mod macros;
+mod privilege;
+mod session_memory;
+mod signal;
+mod tui;
+
+use std::path::PathBuf;
+
+/// Pulls `--device <path>` out of the process arguments, if present.
+/// Nothing else on the command line is recognized yet, so this is a plain
+/// scan rather than pulling in a whole argument-parsing crate for one flag.
+fn parse_device_arg() -> Option<PathBuf> {
+    let mut args = std::env::args();
+    while let Some(arg) = args.next() {
+        if arg == "--device" {
+            return args.next().map(PathBuf::from);
+        }
+    }
+    None
+}
+
+fn main() -> anyhow::Result<()> {
+    logger::init_logger()?;
+    privilege::ensure_root()?;
+
+    // Best-effort cleanup for the narrow window between logger init and
+    // `tui::set_panic_hook` below: if something panics here, this at least
+    // gets raw mode turned back off, even though nothing has entered the
+    // alternate screen yet.
+    let _early_guard = scopeguard::guard((), |_| {
+        let _ = crossterm::terminal::disable_raw_mode();
+    });
+
+    tui::set_panic_hook();
+    signal::install_handler()?;
+    let result = app::run(parse_device_arg());
+    if let Some(path) = logger::log_file_path() {
+        println!("Log file: {}", path.display());
+    }
+    result
+}
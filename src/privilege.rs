@@ -0,0 +1,63 @@
+//! Ensures the installer runs with root privileges before touching disks or
+//! the filesystem, re-executing itself under `sudo` if it doesn't already
+//! have them.
+
+use std::env;
+use std::os::unix::process::CommandExt as _;
+use std::process::Command;
+
+use anyhow::{Context, Result};
+
+use crate::command::CommandExt;
+
+/// Whether the current process is already running as root. Shells out to
+/// `id -u` rather than a libc binding, consistent with how the rest of this
+/// installer talks to the system (lsblk, sgdisk, wipefs, ...).
+fn is_root() -> Result<bool> {
+    let uid = Command::new("id").arg("-u").read().context("failed to check the current user id")?;
+    Ok(is_root_uid(&uid))
+}
+
+/// Pure parse of `id -u`'s output, extracted so it's testable without
+/// actually shelling out.
+fn is_root_uid(output: &str) -> bool {
+    output.trim() == "0"
+}
+
+/// Guarantees the process is root by the time it returns: a no-op if already
+/// root, otherwise re-execs the current binary under `sudo` with the same
+/// arguments. Uses `exec` (replacing this process) rather than spawning a
+/// child and waiting, so there's never more than one instance running.
+///
+/// The already-root check exists so a session already started as root (e.g.
+/// via `sudo -E` or a root shell) proceeds directly instead of shelling out
+/// to a redundant nested `sudo`, which would prompt for a password again for
+/// no reason.
+pub fn ensure_root() -> Result<()> {
+    if is_root()? {
+        return Ok(());
+    }
+
+    let exe = env::current_exe().context("failed to resolve the installer's own executable path")?;
+    let args: Vec<String> = env::args().skip(1).collect();
+
+    // If `exec` returns at all, it failed (no `sudo`, permission denied,
+    // ...); there's no "fall through and run un-escalated" case.
+    let error = Command::new("sudo").arg(exe).args(args).exec();
+    Err(error).context("failed to re-exec under sudo")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn uid_zero_is_root() {
+        assert!(is_root_uid("0\n"));
+    }
+
+    #[test]
+    fn a_non_zero_uid_is_not_root() {
+        assert!(!is_root_uid("1000\n"));
+    }
+}
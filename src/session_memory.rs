@@ -0,0 +1,64 @@
+//! Small opt-in "restore last session" file: remembers the previous run's
+//! `Config` and target disk so a reinstall/test loop doesn't have to redo
+//! every selection from scratch, and doubles as a crash-recovery net since
+//! `app::guide` now writes it on every change rather than only on a clean
+//! exit. Never applied automatically — `app::guide` always prompts before
+//! restoring it, so a stale file can't silently steer a fresh install onto
+//! the wrong disk.
+
+use std::path::PathBuf;
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+
+use crate::config::Config;
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct SessionMemory {
+    pub config: Config,
+    pub disk_path: Option<PathBuf>,
+}
+
+impl SessionMemory {
+    /// Loads the last session's memory, if any. Returns `None` for a missing
+    /// or corrupt file rather than failing startup — like `Favorites::load`,
+    /// this is a convenience, not something worth erroring over.
+    pub fn load() -> Option<Self> {
+        let contents = std::fs::read_to_string(path()).ok()?;
+        serde_json::from_str(&contents).ok()
+    }
+
+    pub fn save(&self) -> Result<()> {
+        let path = path();
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+}
+
+/// `~/.config/artixide/session.json`, alongside `favorites.json`.
+fn path() -> PathBuf {
+    let base = std::env::var_os("HOME").map(PathBuf::from).unwrap_or_else(std::env::temp_dir);
+    base.join(".config/artixide/session.json")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_through_json() {
+        let memory = SessionMemory {
+            config: Config { keyboard_layout: Some("us".into()), ..Default::default() },
+            disk_path: Some(PathBuf::from("/dev/sda")),
+        };
+
+        let json = serde_json::to_string(&memory).unwrap();
+        let restored: SessionMemory = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(restored.disk_path, Some(PathBuf::from("/dev/sda")));
+        assert_eq!(restored.config.keyboard_layout, Some("us".into()));
+    }
+}
@@ -0,0 +1,27 @@
+//! Restores the terminal before exiting if the process receives SIGINT or
+//! SIGTERM (`kill`, a closing terminal, ...), instead of leaving the user's
+//! shell stuck in raw/alternate-screen mode. Ctrl+C alone isn't enough to
+//! cover this: in raw mode it arrives as an ordinary key event rather than a
+//! signal, but `kill`/a closing terminal emulator send a real signal that
+//! nothing in the render loop ever sees.
+
+use anyhow::{Context, Result};
+use signal_hook::consts::{SIGINT, SIGTERM};
+use signal_hook::iterator::Signals;
+
+/// Spawns a background thread that waits for SIGINT/SIGTERM and restores the
+/// terminal before exiting the whole process. Installed once at startup
+/// alongside `tui::set_panic_hook`, so both teardown paths (a panic, an
+/// external signal) are covered.
+pub fn install_handler() -> Result<()> {
+    let mut signals = Signals::new([SIGINT, SIGTERM]).context("failed to register the SIGINT/SIGTERM handler")?;
+    std::thread::spawn(move || {
+        if signals.forever().next().is_some() {
+            crate::tui::restore_terminal_for_signal();
+            // 128 + signal number is the conventional exit code for "killed
+            // by a signal"; SIGINT is what actually arrives most often.
+            std::process::exit(130);
+        }
+    });
+    Ok(())
+}
@@ -0,0 +1,110 @@
+//! Terminal setup/teardown for the ratatui + crossterm backend.
+
+pub mod data;
+pub mod views;
+pub mod widgets;
+
+use std::io::{self, Stdout};
+use std::ops::{Deref, DerefMut};
+use std::process::Command;
+
+use anyhow::Result;
+use crossterm::{
+    event::{DisableBracketedPaste, DisableMouseCapture, EnableBracketedPaste, EnableMouseCapture},
+    execute,
+    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
+};
+use ratatui::{backend::CrosstermBackend, Terminal};
+
+pub type Tui = Terminal<CrosstermBackend<Stdout>>;
+
+/// Puts the terminal into raw + alternate-screen mode on construction and
+/// guarantees `destroy` runs on `Drop`, regardless of whether the caller
+/// returns normally, propagates an error, or panics (Rust runs destructors
+/// while unwinding). Replaces manually pairing `tui::init`/`tui::destroy`
+/// across every exit path, which `guide` and the panic hook previously had
+/// to get right by hand.
+pub struct TerminalGuard {
+    terminal: Tui,
+    /// Whether `Drop` clears the real TTY after leaving the alternate screen.
+    /// Defaults to `true`; callers that want trailing output (an error, a
+    /// final config dump) to stay visible after exit should set this to
+    /// `false` before the guard drops.
+    clear_on_exit: bool,
+}
+
+impl TerminalGuard {
+    pub fn new() -> Result<Self> {
+        enable_raw_mode()?;
+        execute!(io::stdout(), EnterAlternateScreen, EnableBracketedPaste, EnableMouseCapture)?;
+        let terminal = Terminal::new(CrosstermBackend::new(io::stdout()))?;
+        Ok(Self { terminal, clear_on_exit: true })
+    }
+
+    pub fn set_clear_on_exit(&mut self, clear_on_exit: bool) {
+        self.clear_on_exit = clear_on_exit;
+    }
+}
+
+impl Deref for TerminalGuard {
+    type Target = Tui;
+
+    fn deref(&self) -> &Tui {
+        &self.terminal
+    }
+}
+
+impl DerefMut for TerminalGuard {
+    fn deref_mut(&mut self) -> &mut Tui {
+        &mut self.terminal
+    }
+}
+
+impl Drop for TerminalGuard {
+    fn drop(&mut self) {
+        destroy(self.clear_on_exit);
+    }
+}
+
+/// Leaves raw/alternate-screen mode and, if `clear` is set, clears the real
+/// TTY too. Idempotent and infallible-in-practice (errors are swallowed) so
+/// it's safe to call from `TerminalGuard::drop` and the panic hook without
+/// worrying about ordering between the two.
+///
+/// `LeaveAlternateScreen` already restores whatever was on the real terminal
+/// before the installer started; the extra `clear` is only for wiping the
+/// installer's own leftover output on a normal, successful exit. Skip it
+/// when the caller wants that output (an error, a final summary) to stay
+/// readable after exit.
+fn destroy(clear: bool) {
+    let _ = disable_raw_mode();
+    let _ = execute!(io::stdout(), DisableBracketedPaste, DisableMouseCapture, LeaveAlternateScreen);
+    if clear {
+        let _ = Command::new("clear").status();
+    }
+}
+
+/// Restores the terminal from a context with no `TerminalGuard` to hand,
+/// e.g. a signal handler running on its own thread after SIGINT/SIGTERM.
+/// Never clears, matching `set_panic_hook`'s reasoning: whatever's on screen
+/// when the process is killed should stay readable, not get wiped.
+pub fn restore_terminal_for_signal() {
+    destroy(false);
+}
+
+/// Installs a panic hook that restores the terminal before printing the
+/// panic message, so a panic mid-render is visible instead of scribbled into
+/// the alternate screen. Safe to call unconditionally even if no
+/// `TerminalGuard` is alive yet (it just becomes a no-op past the first
+/// `disable_raw_mode`/`LeaveAlternateScreen` call). Never clears, so the
+/// panic message printed right after isn't wiped along with it.
+pub fn set_panic_hook() {
+    let default_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        destroy(false);
+        default_hook(info);
+        if let Some(path) = crate::logger::log_file_path() {
+            eprintln!("Log file: {}", path.display());
+        }
+    }));
+}
@@ -0,0 +1,2 @@
+pub mod partition;
+pub mod system;
@@ -0,0 +1,234 @@
+//! Disk/partition data model shared by the partition view and the apply flow.
+
+pub mod gpt;
+pub mod impls;
+pub mod lsblk;
+
+use std::path::PathBuf;
+use std::str::FromStr;
+
+pub use impls::{
+    parse_mkfs_options, CompatDevice, DiskSpace, FileSystem, FstabKeyMode, GptAttributes, MemPartition, MemTableEntry,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TableType {
+    Gpt,
+    Mbr,
+    None,
+}
+
+/// A disk as reported by `lsblk`, independent of whether we can safely edit
+/// its partition table.
+#[derive(Debug, Clone)]
+pub struct Disk {
+    pub path: PathBuf,
+    pub model: String,
+    /// Total size in bytes.
+    pub size: u64,
+    pub sector_size: u64,
+    pub table_type: TableType,
+    /// Start of the usable range. For GPT this is `first_usable_lba - 1`, one
+    /// before the first usable sector, and `fill_free_space` starts its GPT
+    /// walk at `starting_lba + 1`; for MBR it is `0` and the walk starts there.
+    pub starting_lba: u64,
+    /// One past the last sector usable for a partition. For GPT this is
+    /// `last_usable_lba + 1`; for MBR it is `size / sector_size`.
+    pub ending_lba: u64,
+    /// Set when the boot sector carries more than one non-zero MBR partition
+    /// entry alongside the `0xEE` protective type — a hybrid MBR. We refuse
+    /// to edit these: rewriting the GPT without understanding the hand-tuned
+    /// MBR entries risks corrupting whichever OS depends on them.
+    pub hybrid_mbr: bool,
+}
+
+/// What kind of boot-sector MBR a device has, independent of whether its real
+/// partition table is GPT.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MbrKind {
+    /// No MBR signature at all.
+    None,
+    /// A single `0xEE` entry spanning the disk — the normal GPT protective
+    /// MBR, safe to preserve as-is.
+    Protective,
+    /// A `0xEE` entry alongside other non-zero entries — a hand-crafted
+    /// hybrid MBR used by some dual-boot/legacy setups.
+    Hybrid,
+    /// A real MBR/dos partition table (no `0xEE` entry).
+    Dos,
+}
+
+const MBR_PROTECTIVE_TYPE: u8 = 0xEE;
+
+/// Inspects the boot sector's four MBR partition-table entries (offset 446,
+/// 16 bytes each) to classify the MBR. `sector` must be at least 512 bytes.
+pub fn detect_mbr_kind(sector: &[u8]) -> MbrKind {
+    if sector.len() < 512 || sector[510] != 0x55 || sector[511] != 0xAA {
+        return MbrKind::None;
+    }
+
+    let types: Vec<u8> = (0..4)
+        .map(|i| sector[446 + i * 16 + 4])
+        .filter(|&ty| ty != 0)
+        .collect();
+
+    match types.as_slice() {
+        [] => MbrKind::None,
+        [MBR_PROTECTIVE_TYPE] => MbrKind::Protective,
+        types if types.contains(&MBR_PROTECTIVE_TYPE) => MbrKind::Hybrid,
+        _ => MbrKind::Dos,
+    }
+}
+
+/// A device, split into whether the partitioner can edit it. `Incompatible`
+/// covers disks with no partition table yet, or ones we intentionally refuse
+/// to touch.
+#[derive(Debug, Clone)]
+pub enum Device {
+    Compatible(CompatDevice),
+    Incompatible(Disk),
+}
+
+impl Disk {
+    /// Whether this disk's partition table is GPT, as opposed to `Mbr` or
+    /// `None`. `fill_free_space`/numbering branch on this since GPT's flat
+    /// 128-entry table and MBR's 4-primary/extended/logical structure need
+    /// different layout logic.
+    pub fn is_gpt(&self) -> bool {
+        self.table_type == TableType::Gpt
+    }
+}
+
+impl Device {
+    pub fn disk(&self) -> &Disk {
+        match self {
+            Device::Compatible(dev) => &dev.disk,
+            Device::Incompatible(disk) => disk,
+        }
+    }
+
+    /// Builds a `Device` from an already-parsed `Disk` and its boot sector,
+    /// marking hybrid-MBR devices `Incompatible` (with a logged warning)
+    /// regardless of `table_type`, since editing those risks corrupting the
+    /// hand-crafted MBR entries.
+    pub fn new_from(mut disk: Disk, boot_sector: &[u8], partitions: Vec<MemPartition>) -> Device {
+        let mbr_kind = detect_mbr_kind(boot_sector);
+        disk.hybrid_mbr = mbr_kind == MbrKind::Hybrid;
+
+        log::debug!("{}: table={:?} mbr={:?}", disk.path.display(), disk.table_type, mbr_kind);
+
+        if disk.hybrid_mbr {
+            log::warn!(
+                "{}: detected a hybrid MBR; refusing to edit this device to avoid corrupting it",
+                disk.path.display()
+            );
+            return Device::Incompatible(disk);
+        }
+
+        if disk.table_type == TableType::None {
+            return Device::Incompatible(disk);
+        }
+
+        Device::Compatible(CompatDevice::new(disk, partitions))
+    }
+}
+
+/// Byte-size formatting/parsing convention for the whole UI: sizes are always
+/// displayed and parsed as IEC (binary, 1024-based) units. A size typed as
+/// `10GB` is treated identically to `10GiB` — we accept the SI-looking
+/// spelling as an alias rather than interpreting it as decimal — so display
+/// (`format_size`) and the create-prompt parser (`ByteSize::from_str`) always
+/// agree on the resulting sector count.
+pub fn format_size(bytes: u64) -> String {
+    const UNITS: &[&str] = &["B", "KiB", "MiB", "GiB", "TiB", "PiB"];
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{bytes} {}", UNITS[0])
+    } else {
+        format!("{size:.2} {}", UNITS[unit])
+    }
+}
+
+/// A parsed size, always normalized to bytes under the IEC convention
+/// documented on `format_size`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ByteSize(pub u64);
+
+impl FromStr for ByteSize {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let split_at = s
+            .find(|c: char| !c.is_ascii_digit() && c != '.')
+            .unwrap_or(s.len());
+        let (number, unit) = s.split_at(split_at);
+        let number: f64 = number
+            .parse()
+            .map_err(|_| anyhow::anyhow!("'{s}' is not a valid size"))?;
+        let multiplier: u64 = match unit.trim().to_ascii_uppercase().as_str() {
+            "" | "B" => 1,
+            "K" | "KB" | "KIB" => 1024,
+            "M" | "MB" | "MIB" => 1024u64.pow(2),
+            "G" | "GB" | "GIB" => 1024u64.pow(3),
+            "T" | "TB" | "TIB" => 1024u64.pow(4),
+            other => anyhow::bail!("'{other}' is not a recognized size unit"),
+        };
+        Ok(ByteSize((number * multiplier as f64) as u64))
+    }
+}
+
+impl ByteSize {
+    pub fn to_sectors(self, sector_size: u64) -> u64 {
+        self.0 / sector_size
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn iec_and_si_spellings_agree() {
+        let iec: ByteSize = "10GiB".parse().unwrap();
+        let si: ByteSize = "10GB".parse().unwrap();
+        assert_eq!(iec, si);
+        assert_eq!(iec.to_sectors(512), si.to_sectors(512));
+    }
+
+    #[test]
+    fn parses_fractional_sizes() {
+        let size: ByteSize = "1.5GiB".parse().unwrap();
+        assert_eq!(size.0, (1.5 * 1024.0 * 1024.0 * 1024.0) as u64);
+    }
+
+    #[test]
+    fn format_size_round_trips_gib() {
+        let bytes = 10u64 * 1024 * 1024 * 1024;
+        assert_eq!(format_size(bytes), "10.00 GiB");
+    }
+
+    /// A 16 TiB disk (well within a modern drive's range) is ~1.76e13 bytes —
+    /// nowhere near `f64`'s 2^53 exact-integer ceiling (~9e15), so the
+    /// `bytes as f64` conversion here doesn't lose precision. Guards against
+    /// a future switch to a narrower float, or a much larger synthetic disk,
+    /// silently rounding the displayed size.
+    #[test]
+    fn format_size_reports_exact_tib_for_a_16tib_disk() {
+        let bytes = 16u64 * 1024 * 1024 * 1024 * 1024;
+        assert_eq!(format_size(bytes), "16.00 TiB");
+    }
+
+    #[test]
+    fn parses_a_16tib_size_expression_without_precision_loss() {
+        let size: ByteSize = "16TiB".parse().unwrap();
+        assert_eq!(size.0, 16u64 * 1024 * 1024 * 1024 * 1024);
+        // 4096-byte sectors, as a 4Kn drive at this size would use.
+        assert_eq!(size.to_sectors(4096), (16u64 * 1024 * 1024 * 1024 * 1024) / 4096);
+    }
+}
@@ -0,0 +1,175 @@
+//! Reads a device's usable-LBA range and existing partition entries from its
+//! GPT header. Behind a trait so `lsblk::get_devices` can inject the real
+//! `gptman`-backed reader while tests supply synthetic values instead of
+//! needing an actual disk.
+
+use std::path::Path;
+
+use anyhow::Context;
+use gptman::GPT;
+
+use super::{FileSystem, GptAttributes, MemPartition};
+
+/// Supplies the `(starting_lba, ending_lba)` pair for a GPT disk, matching
+/// the convention documented on `Disk::starting_lba`/`ending_lba`
+/// (`first_usable_lba - 1`, `last_usable_lba + 1`), and the disk's existing
+/// partition entries so `build_real_device` doesn't have to treat an
+/// already-partitioned disk as one big free region.
+pub trait UsableRangeSource {
+    fn usable_range(&self, path: &Path) -> anyhow::Result<(u64, u64)>;
+
+    /// Reads this GPT disk's used partition entries, converting each into a
+    /// `MemPartition` with `real: true`. Defaulted to "no partitions" so
+    /// test doubles that only care about `usable_range` don't have to
+    /// implement this too.
+    fn read_partitions(&self, _path: &Path) -> anyhow::Result<Vec<MemPartition>> {
+        Ok(Vec::new())
+    }
+}
+
+/// Reads the real GPT header via `gptman`. Only meaningful for GPT disks;
+/// callers skip it for MBR/no-table devices, whose usable range is derived
+/// from `size`/`sector_size` alone.
+pub struct GptReader;
+
+impl UsableRangeSource for GptReader {
+    fn usable_range(&self, path: &Path) -> anyhow::Result<(u64, u64)> {
+        let mut file = std::fs::File::open(path)
+            .with_context(|| format!("failed to open {} to read its GPT header", path.display()))?;
+        let gpt = GPT::find_from(&mut file)
+            .with_context(|| format!("failed to read the GPT header on {}", path.display()))?;
+        Ok(usable_range_from_lbas(gpt.header.first_usable_lba, gpt.header.last_usable_lba))
+    }
+
+    fn read_partitions(&self, path: &Path) -> anyhow::Result<Vec<MemPartition>> {
+        let mut file = std::fs::File::open(path)
+            .with_context(|| format!("failed to open {} to read its GPT partitions", path.display()))?;
+        let gpt = GPT::find_from(&mut file)
+            .with_context(|| format!("failed to read the GPT partition entries on {}", path.display()))?;
+
+        Ok(gpt
+            .iter()
+            .filter(|(_, partition)| partition.is_used())
+            .map(|(number, partition)| MemPartition {
+                number,
+                start: partition.starting_lba,
+                sectors: partition.ending_lba - partition.starting_lba + 1,
+                filesystem: FileSystem::from_gpt_type_guid(&type_guid_to_string(&partition.partition_type_guid)),
+                label: None,
+                mountpoint: None,
+                mkfs_options: None,
+                gpt_attributes: GptAttributes::default(),
+                real: true,
+            })
+            .collect())
+    }
+}
+
+/// Converts a raw 16-byte GPT partition-type GUID into the canonical
+/// dash-separated, uppercase string form (matching what
+/// `FileSystem::gpt_type_guid` returns), applying the GUID spec's
+/// mixed-endian byte order: the first three groups are stored
+/// little-endian, the last two groups big-endian.
+pub(crate) fn type_guid_to_string(bytes: &[u8; 16]) -> String {
+    format!(
+        "{:02X}{:02X}{:02X}{:02X}-{:02X}{:02X}-{:02X}{:02X}-{:02X}{:02X}-{:02X}{:02X}{:02X}{:02X}{:02X}{:02X}",
+        bytes[3],
+        bytes[2],
+        bytes[1],
+        bytes[0],
+        bytes[5],
+        bytes[4],
+        bytes[7],
+        bytes[6],
+        bytes[8],
+        bytes[9],
+        bytes[10],
+        bytes[11],
+        bytes[12],
+        bytes[13],
+        bytes[14],
+        bytes[15]
+    )
+}
+
+/// Inverse of [`type_guid_to_string`], used by `apply::write_partition_table`
+/// to turn `FileSystem::gpt_type_guid`'s dashed string back into the raw
+/// bytes gptman writes to the partition entry. Malformed input (wrong length
+/// or non-hex characters) comes back as the all-zero "unused" GUID rather
+/// than erroring — callers only ever pass one of `gpt_type_guid`'s own
+/// fixed strings, so this is a defensive fallback, not a validated parser.
+pub(crate) fn type_guid_from_string(guid: &str) -> [u8; 16] {
+    let hex: Vec<u8> = guid
+        .chars()
+        .filter(|c| *c != '-')
+        .collect::<String>()
+        .as_bytes()
+        .chunks(2)
+        .filter_map(|pair| std::str::from_utf8(pair).ok().and_then(|pair| u8::from_str_radix(pair, 16).ok()))
+        .collect();
+
+    let mut bytes = [0u8; 16];
+    if hex.len() == 16 {
+        bytes[0] = hex[3];
+        bytes[1] = hex[2];
+        bytes[2] = hex[1];
+        bytes[3] = hex[0];
+        bytes[4] = hex[5];
+        bytes[5] = hex[4];
+        bytes[6] = hex[7];
+        bytes[7] = hex[6];
+        bytes[8..16].copy_from_slice(&hex[8..16]);
+    }
+    bytes
+}
+
+/// Converts GPT's inclusive `first_usable_lba`/`last_usable_lba` into the
+/// `(starting_lba, ending_lba)` convention `Disk` uses everywhere else:
+/// `starting_lba` is one *before* the first usable sector and `ending_lba` is
+/// one *past* the last, so `fill_free_space`'s GPT walk over
+/// `[starting_lba + 1, ending_lba)` covers exactly the usable sectors.
+///
+/// MBR disks don't need this adjustment — `lsblk::fill_usable_range` gives
+/// `starting_lba = 0` and `ending_lba = size / sector_size`, which is already
+/// an exclusive whole-disk range with nothing to shift.
+///
+/// Extracted from `GptReader::usable_range` so the ±1 math itself — the part
+/// that's easy to get backwards — is testable without an actual GPT-formatted
+/// disk image.
+fn usable_range_from_lbas(first_usable_lba: u64, last_usable_lba: u64) -> (u64, u64) {
+    (first_usable_lba - 1, last_usable_lba + 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn known_gptman_header_values_convert_correctly() {
+        // A typical 512-byte-sector, 1 MiB-aligned GPT disk: gptman reports
+        // first_usable_lba = 34 (past the protective MBR, primary header, and
+        // 128-entry partition array) and last_usable_lba as the sector just
+        // before the backup partition array/header.
+        assert_eq!(usable_range_from_lbas(34, 2097086), (33, 2097087));
+    }
+
+    #[test]
+    fn type_guid_round_trips_through_string_and_back() {
+        for guid in [
+            FileSystem::Fat32.gpt_type_guid(),
+            FileSystem::Ext4.gpt_type_guid(),
+            FileSystem::Swap.gpt_type_guid(),
+        ] {
+            let bytes = type_guid_from_string(guid);
+            assert_eq!(type_guid_to_string(&bytes), guid);
+        }
+    }
+
+    #[test]
+    fn four_kn_sector_disk_shifts_by_one_sector_not_one_byte() {
+        // 4Kn disks have a much smaller usable-LBA range in absolute sector
+        // counts, but the ±1 adjustment is a sector count, not a byte count,
+        // so it applies identically regardless of sector size.
+        assert_eq!(usable_range_from_lbas(6, 262141), (5, 262142));
+    }
+}
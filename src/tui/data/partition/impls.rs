@@ -0,0 +1,1364 @@
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use strum::AsRefStr;
+
+use crate::command::CommandExt;
+
+use super::{Disk, TableType};
+
+/// How long `format_partition` and `shrink_filesystem` wait for `mkfs`,
+/// `resize2fs`, or `btrfs filesystem resize` before giving up on a device
+/// that's stopped responding (a failing drive, a stuck USB controller).
+/// These touch real hardware and can hang indefinitely, unlike the rest of
+/// this module's `Command` usage.
+const FILESYSTEM_COMMAND_TIMEOUT: Duration = Duration::from_secs(300);
+
+/// How `generate_fstab` keys the `<device>` column of each entry. `Uuid` is
+/// the default since it's the only one of the three `blkid`-backed modes
+/// that survives a partition being recreated at the same offset with a
+/// different label — an install guide's standard recommendation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, AsRefStr, Serialize, Deserialize)]
+pub enum FstabKeyMode {
+    #[default]
+    Uuid,
+    Label,
+    PartUuid,
+    Path,
+}
+
+impl FstabKeyMode {
+    /// Every mode, in the order offered to the user by the fstab-mode picker.
+    pub fn selectable() -> &'static [FstabKeyMode] {
+        &[FstabKeyMode::Uuid, FstabKeyMode::Label, FstabKeyMode::PartUuid, FstabKeyMode::Path]
+    }
+
+    /// The `blkid -s <tag>` value to look up, or `None` for `Path`, which
+    /// doesn't go through `blkid` at all.
+    fn blkid_tag(self) -> Option<&'static str> {
+        match self {
+            FstabKeyMode::Uuid => Some("UUID"),
+            FstabKeyMode::Label => Some("LABEL"),
+            FstabKeyMode::PartUuid => Some("PARTUUID"),
+            FstabKeyMode::Path => None,
+        }
+    }
+}
+
+/// Resolves `path`'s fstab device-column key for `mode`, falling back to the
+/// raw device path when `blkid` fails or comes back empty — an unformatted
+/// or just-created partition has no UUID/LABEL/PARTUUID yet, and falling
+/// back keeps the preview useful instead of producing a blank entry.
+fn fstab_device_key(path: &Path, mode: FstabKeyMode) -> String {
+    let Some(tag) = mode.blkid_tag() else {
+        return path.display().to_string();
+    };
+    match Command::new("blkid").args(["-s", tag, "-o", "value"]).arg(path).read() {
+        Ok(value) if !value.trim().is_empty() => format!("{tag}={}", value.trim()),
+        _ => path.display().to_string(),
+    }
+}
+
+/// Partition type GUID GRUB looks for when installing to a GPT disk under
+/// legacy BIOS. Without a partition carrying this GUID, `grub-install`
+/// refuses outright — there's nowhere for its core image to live, since GPT
+/// (unlike MBR) leaves no post-MBR embedding gap.
+pub const BIOS_BOOT_GUID: &str = "21686148-6449-6E6F-744E-656E64696D45";
+
+/// Label `suggested_layout` gives the BIOS-boot partition it creates, used
+/// to recognize an existing one since `MemPartition` doesn't track type GUIDs.
+const BIOS_BOOT_LABEL: &str = "BIOSBOOT";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, AsRefStr)]
+pub enum FileSystem {
+    Fat32,
+    Ext4,
+    Btrfs,
+    Swap,
+    Unknown,
+}
+
+impl FileSystem {
+    /// One-line description shown next to this option in the create-flow
+    /// type picker, and below it for the highlighted entry, so newcomers
+    /// don't need to consult external docs mid-install. Keep each entry
+    /// short enough to fit the popup.
+    pub fn description(self) -> &'static str {
+        match self {
+            FileSystem::Fat32 => "EFI system / cross-platform",
+            FileSystem::Ext4 => "general Linux root",
+            FileSystem::Btrfs => "Linux root with snapshots/subvolumes",
+            FileSystem::Swap => "paging",
+            FileSystem::Unknown => "unrecognized or unformatted",
+        }
+    }
+
+    /// Filesystems offered in the create-flow type picker. `Unknown` isn't a
+    /// real choice — it's how an already-existing, unrecognized filesystem is
+    /// reported — so it's excluded here.
+    pub fn selectable() -> &'static [FileSystem] {
+        &[FileSystem::Fat32, FileSystem::Ext4, FileSystem::Btrfs, FileSystem::Swap]
+    }
+
+    /// Practical minimum size for a new partition of this filesystem, below
+    /// which mkfs either refuses outright or produces something too small to
+    /// be useful. Checked by `DiskEditor::handle_create` so "I made a 1 MiB
+    /// btrfs" fails with a clear message instead of a cryptic mkfs error at
+    /// apply time.
+    pub fn minimum_bytes(self) -> u64 {
+        const MIB: u64 = 1024 * 1024;
+        match self {
+            FileSystem::Fat32 => 33 * MIB,
+            FileSystem::Ext4 => 16 * MIB,
+            FileSystem::Btrfs => 256 * MIB,
+            FileSystem::Swap => MIB,
+            FileSystem::Unknown => 0,
+        }
+    }
+
+    /// Practical maximum size for a partition of this filesystem, above
+    /// which mkfs either refuses outright or the result isn't addressable by
+    /// the filesystem's own on-disk format. Checked by
+    /// `DiskEditor::handle_create` alongside `minimum_bytes`, so "I made a 4
+    /// TiB FAT32 ESP" fails here with a clear message instead of a cryptic
+    /// mkfs error at apply time. `None` means no practical ceiling worth
+    /// enforcing.
+    pub fn maximum_bytes(self) -> Option<u64> {
+        const TIB: u64 = 1024 * 1024 * 1024 * 1024;
+        match self {
+            // FAT32's 32-bit sector count field tops out at 2 TiB with the
+            // common 512-byte sectors; mkfs.fat also just refuses well
+            // before the theoretical ceiling, so this is the practical limit
+            // installers document.
+            FileSystem::Fat32 => Some(2 * TIB),
+            FileSystem::Ext4 => Some(16 * TIB),
+            FileSystem::Btrfs => None,
+            FileSystem::Swap => None,
+            FileSystem::Unknown => None,
+        }
+    }
+
+    /// Longest volume label this filesystem's on-disk format can store, in
+    /// bytes. Checked by `DiskEditor::handle_set_label` so a label rejected
+    /// here fails with a clear message instead of mkfs silently truncating
+    /// (FAT) or refusing (ext4/btrfs) it at apply time.
+    pub fn max_label_len(self) -> usize {
+        match self {
+            // FAT's volume label is a fixed 11-byte field, but mkfs.fat also
+            // accepts (and space-pads) up to 11 characters of the longer
+            // "extended" label some tools show — keep it at the field's real
+            // limit rather than that appearance.
+            FileSystem::Fat32 => 11,
+            FileSystem::Ext4 => 16,
+            FileSystem::Btrfs => 255,
+            FileSystem::Swap => 15,
+            FileSystem::Unknown => 0,
+        }
+    }
+
+    /// GPT partition-type GUID `apply` writes for a partition of this
+    /// filesystem, so it shows up correctly typed to other OSes/tools and so
+    /// `apply::verify_applied` can confirm the write actually landed.
+    /// `Ext4`/`Btrfs` share the generic "Linux filesystem data" GUID — GPT
+    /// doesn't distinguish Linux filesystems any further than that.
+    pub fn gpt_type_guid(self) -> &'static str {
+        match self {
+            FileSystem::Fat32 => "C12A7328-F81F-11D2-BA4B-00A0C93EC93B",
+            FileSystem::Ext4 | FileSystem::Btrfs => "0FC63DAF-8483-4772-8E79-3D69D8477DE4",
+            FileSystem::Swap => "0657FD6D-A4AB-43C4-84E5-0933C84B4F4F",
+            FileSystem::Unknown => "00000000-0000-0000-0000-000000000000",
+        }
+    }
+
+    /// Reverse of [`FileSystem::gpt_type_guid`], used when reading an
+    /// existing partition's type GUID back off a real GPT table.
+    /// `Ext4`/`Btrfs` share a GUID, so a filesystem created as `Btrfs` reads
+    /// back as `Ext4` here — GPT's type GUID alone can't tell them apart, and
+    /// there's no on-disk field to disambiguate. Any GUID this crate doesn't
+    /// write itself, including GPT's various non-Linux type GUIDs, reads back
+    /// as `Unknown`.
+    pub fn from_gpt_type_guid(guid: &str) -> FileSystem {
+        match guid {
+            "C12A7328-F81F-11D2-BA4B-00A0C93EC93B" => FileSystem::Fat32,
+            "0FC63DAF-8483-4772-8E79-3D69D8477DE4" => FileSystem::Ext4,
+            "0657FD6D-A4AB-43C4-84E5-0933C84B4F4F" => FileSystem::Swap,
+            _ => FileSystem::Unknown,
+        }
+    }
+}
+
+/// A span of unallocated sectors on the device, `[start, start + sectors)`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DiskSpace {
+    pub start: u64,
+    pub sectors: u64,
+}
+
+/// The subset of GPT partition-entry attribute bits (UEFI spec table 24) this
+/// installer exposes as user-facing toggles. Bit 0 is "system partition"
+/// (commonly called "required" — firmware/OS must not delete or move it),
+/// bit 2 is "legacy BIOS bootable" (mirrors the MBR active flag for hybrid
+/// boot setups), and bit 63 is "no automount" (a Microsoft-defined bit most
+/// Linux automounters — udisks2, systemd — also honor). The other 61 bits
+/// either aren't standardized or aren't useful to toggle from an installer,
+/// so they're not modeled here; [`GptAttributes::from_bits`] silently drops
+/// them rather than needing a way to round-trip bits this type doesn't know
+/// about.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct GptAttributes {
+    pub required: bool,
+    pub no_automount: bool,
+    pub legacy_bios_bootable: bool,
+}
+
+impl GptAttributes {
+    const REQUIRED_BIT: u64 = 1 << 0;
+    const LEGACY_BIOS_BOOTABLE_BIT: u64 = 1 << 2;
+    const NO_AUTOMOUNT_BIT: u64 = 1 << 63;
+
+    /// Packs these flags into a GPT partition entry's 64-bit attribute field,
+    /// as `apply` will write it via gptman.
+    pub fn to_bits(self) -> u64 {
+        let mut bits = 0u64;
+        if self.required {
+            bits |= Self::REQUIRED_BIT;
+        }
+        if self.legacy_bios_bootable {
+            bits |= Self::LEGACY_BIOS_BOOTABLE_BIT;
+        }
+        if self.no_automount {
+            bits |= Self::NO_AUTOMOUNT_BIT;
+        }
+        bits
+    }
+
+    /// Unpacks the bits this type models from a raw GPT attribute field, e.g.
+    /// one read back from an existing partition. Bits outside the three above
+    /// are ignored.
+    pub fn from_bits(bits: u64) -> Self {
+        Self {
+            required: bits & Self::REQUIRED_BIT != 0,
+            legacy_bios_bootable: bits & Self::LEGACY_BIOS_BOOTABLE_BIT != 0,
+            no_automount: bits & Self::NO_AUTOMOUNT_BIT != 0,
+        }
+    }
+
+    /// Comma-separated summary for `details_text`, e.g. `"required,
+    /// no-automount"`. Empty when no flag is set, so callers can skip the
+    /// line entirely rather than showing a bare label.
+    pub fn label(self) -> String {
+        let mut flags = Vec::new();
+        if self.required {
+            flags.push("required");
+        }
+        if self.no_automount {
+            flags.push("no-automount");
+        }
+        if self.legacy_bios_bootable {
+            flags.push("legacy BIOS bootable");
+        }
+        flags.join(", ")
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct MemPartition {
+    pub number: u32,
+    pub start: u64,
+    pub sectors: u64,
+    pub filesystem: FileSystem,
+    pub label: Option<String>,
+    pub mountpoint: Option<String>,
+    /// Extra flags appended to the `mkfs.<fs>` invocation when this partition
+    /// is formatted, e.g. `-m 0` for ext4 or `-n 32k` for btrfs. Parsed with
+    /// [`parse_mkfs_options`] and passed as separate argv entries, never
+    /// through a shell, so it can't smuggle in a `;` or `$()`.
+    pub mkfs_options: Option<String>,
+    /// GPT attribute-bit toggles (required/no-automount/legacy BIOS
+    /// bootable) `apply` writes via gptman. Meaningless on an MBR device —
+    /// `DiskEditor` only exposes the toggle UI for `TableType::Gpt`.
+    pub gpt_attributes: GptAttributes,
+    /// Whether this partition already exists on disk (read from the device's
+    /// current table) as opposed to only planned in `mem_table` pending
+    /// `apply`. Operations that touch the real filesystem, like
+    /// [`shrink_filesystem`], only make sense for real partitions.
+    pub real: bool,
+}
+
+impl MemPartition {
+    pub fn is_real(&self) -> bool {
+        self.real
+    }
+
+    /// Device node for a real partition, e.g. `/dev/sda1` for partition 1 of
+    /// `/dev/sda`. Meaningless for a planned (non-`real`) partition.
+    fn device_path(&self, disk: &Disk) -> PathBuf {
+        let disk_path = disk.path.to_string_lossy();
+        let sep = if disk_path.chars().last().is_some_and(|c| c.is_ascii_digit()) {
+            "p"
+        } else {
+            ""
+        };
+        PathBuf::from(format!("{disk_path}{sep}{}", self.number))
+    }
+}
+
+/// One row of the in-memory partition table: either a real/planned partition
+/// or a gap of free space between them.
+#[derive(Debug, Clone)]
+pub enum MemTableEntry {
+    Partition(MemPartition),
+    Free(DiskSpace),
+}
+
+/// A device we're willing to edit, holding both the on-disk description and
+/// the in-memory table the editor mutates before `apply` commits it.
+#[derive(Debug, Clone)]
+pub struct CompatDevice {
+    pub disk: Disk,
+    pub mem_table: Vec<MemTableEntry>,
+}
+
+/// Filesystems whose on-disk contents [`CompatDevice::shrink_filesystem`]
+/// knows how to resize without destroying data.
+fn supports_online_shrink(filesystem: FileSystem) -> bool {
+    matches!(filesystem, FileSystem::Ext4 | FileSystem::Btrfs)
+}
+
+/// Whether `path` appears as a mount source in `/proc/mounts`.
+fn is_mounted(path: &std::path::Path) -> anyhow::Result<bool> {
+    let mounts = std::fs::read_to_string("/proc/mounts")?;
+    let path = path.to_string_lossy();
+    Ok(mounts.lines().any(|line| line.split_whitespace().next() == Some(path.as_ref())))
+}
+
+/// Splits a `MemPartition::mkfs_options` string into argv entries for
+/// [`CompatDevice::format_partition`]. These are appended to the `mkfs.<fs>`
+/// invocation as separate arguments, never through a shell, so anything that
+/// looks like an attempt at shell syntax (`;`, `|`, `$(...)`, ...) is
+/// rejected up front rather than silently doing nothing.
+pub fn parse_mkfs_options(text: &str) -> anyhow::Result<Vec<String>> {
+    const FORBIDDEN: &[char] = &[';', '|', '&', '$', '`', '>', '<', '\n', '\\', '"', '\''];
+    if let Some(c) = text.chars().find(|c| FORBIDDEN.contains(c)) {
+        anyhow::bail!("mkfs options can't contain '{c}' — pass mkfs flags directly, not shell syntax");
+    }
+    Ok(text.split_whitespace().map(str::to_string).collect())
+}
+
+/// `mkfs`-family program and base flags for `filesystem`. `Unknown` has no
+/// formatter of its own — `format_partition` refuses it before this is ever
+/// consulted.
+fn mkfs_invocation(filesystem: FileSystem) -> (&'static str, &'static [&'static str]) {
+    match filesystem {
+        FileSystem::Fat32 => ("mkfs.fat", &["-F32"]),
+        FileSystem::Ext4 => ("mkfs.ext4", &[]),
+        FileSystem::Btrfs => ("mkfs.btrfs", &[]),
+        FileSystem::Swap => ("mkswap", &[]),
+        FileSystem::Unknown => unreachable!("checked by format_partition's ensure! above"),
+    }
+}
+
+impl CompatDevice {
+    /// Shrinks a real, unmounted, shrink-capable filesystem down to
+    /// `new_sectors`, then updates `partition`'s bookkeeping and regenerates
+    /// `mem_table`'s free-space entries to reflect the newly-freed tail.
+    ///
+    /// This is destructive if interrupted partway, so callers must confirm
+    /// with the user and take a table backup (see
+    /// [`crate::apply::backup_partition_table`]) before calling it. Refuses
+    /// mounted partitions, unknown/unsupported filesystems, and planned
+    /// (non-real) partitions outright.
+    pub fn shrink_filesystem(&mut self, number: u32, new_sectors: u64) -> anyhow::Result<()> {
+        let sector_size = self.disk.sector_size;
+        let disk = self.disk.clone();
+
+        let partition = self
+            .mem_table
+            .iter_mut()
+            .find_map(|entry| match entry {
+                MemTableEntry::Partition(p) if p.number == number => Some(p),
+                _ => None,
+            })
+            .ok_or_else(|| anyhow::anyhow!("no partition numbered {number} on this device"))?;
+
+        anyhow::ensure!(partition.is_real(), "cannot shrink partition {number}: it hasn't been created yet");
+        anyhow::ensure!(
+            supports_online_shrink(partition.filesystem),
+            "shrinking a {} filesystem isn't supported",
+            partition.filesystem.as_ref()
+        );
+        anyhow::ensure!(
+            new_sectors > 0 && new_sectors < partition.sectors,
+            "shrink target must be smaller than the current size"
+        );
+
+        let path = partition.device_path(&disk);
+        anyhow::ensure!(!is_mounted(&path)?, "{} is mounted; unmount it before shrinking", path.display());
+
+        let new_bytes = new_sectors * sector_size;
+        match partition.filesystem {
+            FileSystem::Ext4 => {
+                Command::new("resize2fs")
+                    .arg(&path)
+                    .arg(format!("{}s", new_bytes / 512))
+                    .run_timeout(FILESYSTEM_COMMAND_TIMEOUT)?;
+            }
+            FileSystem::Btrfs => {
+                Command::new("btrfs")
+                    .args(["filesystem", "resize"])
+                    .arg(format!("{new_bytes}"))
+                    .arg(&path)
+                    .run_timeout(FILESYSTEM_COMMAND_TIMEOUT)?;
+            }
+            _ => unreachable!("checked by supports_online_shrink above"),
+        }
+
+        partition.sectors = new_sectors;
+        self.fill_free_space();
+        Ok(())
+    }
+
+    /// Formats a real, on-disk partition with `mkfs.<fs>` (or `mkswap`),
+    /// appending its `mkfs_options` (parsed with [`parse_mkfs_options`]) as
+    /// extra arguments after the filesystem's own base flags. Refuses
+    /// `FileSystem::Unknown`, since there's no formatter for "don't know".
+    pub fn format_partition(&self, number: u32) -> anyhow::Result<()> {
+        let partition = self
+            .mem_table
+            .iter()
+            .find_map(|entry| match entry {
+                MemTableEntry::Partition(p) if p.number == number => Some(p),
+                _ => None,
+            })
+            .ok_or_else(|| anyhow::anyhow!("no partition numbered {number} on this device"))?;
+
+        anyhow::ensure!(
+            partition.filesystem != FileSystem::Unknown,
+            "cannot format partition {number}: no filesystem chosen"
+        );
+
+        let (program, base_args) = mkfs_invocation(partition.filesystem);
+        let extra_args = match &partition.mkfs_options {
+            Some(options) => parse_mkfs_options(options)?,
+            None => Vec::new(),
+        };
+        let path = partition.device_path(&self.disk);
+
+        Command::new(program)
+            .args(base_args)
+            .args(&extra_args)
+            .arg(&path)
+            .run_timeout(FILESYSTEM_COMMAND_TIMEOUT)
+    }
+
+    /// Generates `/etc/fstab` entries for every planned partition that has a
+    /// mountpoint set, in the exact form `apply` will write — so the config
+    /// preview shown to the user can never drift from what actually lands on
+    /// disk. `mode` picks how the `<device>` column is keyed; see
+    /// `fstab_device_key`.
+    pub fn generate_fstab(&self, mode: FstabKeyMode) -> String {
+        let mut lines = vec!["# <device>\t<mountpoint>\t<type>\t<options>\t<dump>\t<pass>".to_string()];
+        for entry in &self.mem_table {
+            let MemTableEntry::Partition(partition) = entry else { continue };
+            let Some(mountpoint) = &partition.mountpoint else { continue };
+            let path = partition.device_path(&self.disk);
+            let device = fstab_device_key(&path, mode);
+            let pass = if mountpoint == "/" { 1 } else { 2 };
+            lines.push(format!(
+                "{device}\t{mountpoint}\t{}\tdefaults\t0\t{pass}",
+                partition.filesystem.as_ref().to_lowercase(),
+            ));
+        }
+        lines.join("\n")
+    }
+}
+
+impl CompatDevice {
+    pub fn new(disk: Disk, partitions: Vec<MemPartition>) -> Self {
+        let mut dev = Self {
+            disk,
+            mem_table: partitions.into_iter().map(MemTableEntry::Partition).collect(),
+        };
+        dev.fill_free_space();
+        dev
+    }
+
+    /// Whether `mem_table` holds a planned partition that hasn't actually
+    /// been written to the disk yet (`MemPartition::real == false`). Backs
+    /// the "unsaved changes" indicator in `PartitionView`, since a planned
+    /// partition is exactly the difference between what's in memory and
+    /// what's really on disk.
+    pub fn is_dirty(&self) -> bool {
+        self.mem_table.iter().any(|entry| matches!(entry, MemTableEntry::Partition(partition) if !partition.is_real()))
+    }
+
+    /// Total bytes committed to partitions (real or only planned) versus
+    /// still free, per `mem_table`. Shown after planning so it's clear how
+    /// much of the disk a plan actually uses before committing to it.
+    pub fn used_and_free_bytes(&self) -> (u64, u64) {
+        let sector_size = self.disk.sector_size;
+        self.mem_table.iter().fold((0, 0), |(used, free), entry| match entry {
+            MemTableEntry::Partition(partition) => (used + partition.sectors * sector_size, free),
+            MemTableEntry::Free(space) => (used, free + space.sectors * sector_size),
+        })
+    }
+
+    /// Marks every planned partition as real, i.e. now on disk. Called after
+    /// a successful `apply` so the "unsaved changes" indicator clears.
+    pub fn mark_applied(&mut self) {
+        for entry in &mut self.mem_table {
+            if let MemTableEntry::Partition(partition) = entry {
+                partition.real = true;
+            }
+        }
+    }
+
+    /// Recomputes the `Free` entries of `mem_table` from the current
+    /// partitions and the disk's usable LBA range, dropping any previous
+    /// `Free` entries first.
+    ///
+    /// Safe against a legacy partition starting at or before
+    /// `disk.starting_lba`: the leading-gap subtraction is guarded by
+    /// `partition.start > cursor`, so it's simply skipped (no leading `Free`
+    /// entry) rather than underflowing.
+    ///
+    /// Branches on `Disk::is_gpt`: GPT's table is flat, while MBR needs the
+    /// primary/extended/logical walk in [`Self::mbr_free_gaps`].
+    pub fn fill_free_space(&mut self) {
+        let mut partitions: Vec<MemPartition> = self
+            .mem_table
+            .drain(..)
+            .filter_map(|entry| match entry {
+                MemTableEntry::Partition(p) => Some(p),
+                MemTableEntry::Free(_) => None,
+            })
+            .collect();
+        partitions.sort_by_key(|p| p.start);
+
+        let gaps = if self.disk.is_gpt() {
+            let spans: Vec<(u64, u64)> = partitions.iter().map(|p| (p.start, p.start + p.sectors)).collect();
+            Self::free_gaps(self.disk.starting_lba + 1, self.disk.ending_lba, &spans)
+        } else {
+            Self::mbr_free_gaps(&self.disk, &partitions)
+        };
+
+        let mut entries: Vec<MemTableEntry> = partitions.into_iter().map(MemTableEntry::Partition).collect();
+        entries.extend(gaps.into_iter().filter(|gap| gap.sectors > 0).map(MemTableEntry::Free));
+        entries.sort_by_key(|entry| match entry {
+            MemTableEntry::Partition(p) => p.start,
+            MemTableEntry::Free(f) => f.start,
+        });
+
+        self.mem_table = entries;
+    }
+
+    /// Walks `spans` (sorted, non-overlapping `(start, end)` ranges) against
+    /// `[range_start, range_end)`, returning the gaps between/around them.
+    /// Shared by the GPT (flat) and MBR (primary-level and inside-extended)
+    /// layout walks so the leading/trailing-gap arithmetic only lives once.
+    fn free_gaps(range_start: u64, range_end: u64, spans: &[(u64, u64)]) -> Vec<DiskSpace> {
+        let mut gaps = Vec::with_capacity(spans.len() + 1);
+        let mut cursor = range_start;
+        for &(start, end) in spans {
+            if start > cursor {
+                gaps.push(DiskSpace { start: cursor, sectors: start - cursor });
+            }
+            cursor = cursor.max(end);
+        }
+        if range_end > cursor {
+            gaps.push(DiskSpace { start: cursor, sectors: range_end - cursor });
+        }
+        gaps
+    }
+
+    /// Sectors reserved ahead of the first logical partition for its EBR
+    /// (extended boot record) — the MBR analogue of a GPT partition entry,
+    /// but stored inline in the extended partition rather than a table.
+    /// Gaps between later logicals are reported in full, with no sector held
+    /// back for their EBRs.
+    const MBR_EBR_SECTORS: u64 = 1;
+
+    /// MBR free-space gaps, honoring the primary/extended/logical structure:
+    /// partition numbers 1-4 are primaries sharing the top-level disk
+    /// timeline, while numbers 5+ are logicals nested inside an implicit
+    /// extended partition — the span from the first logical's reserved EBR
+    /// sector to the end of the last logical. The extended region is folded
+    /// into the top-level walk as a single occupied span (so gaps around it
+    /// come out the same as for any primary), then walked again on its own
+    /// to surface gaps between logicals.
+    fn mbr_free_gaps(disk: &Disk, partitions: &[MemPartition]) -> Vec<DiskSpace> {
+        let primary_spans: Vec<(u64, u64)> =
+            partitions.iter().filter(|p| p.number <= 4).map(|p| (p.start, p.start + p.sectors)).collect();
+        let logicals: Vec<&MemPartition> = partitions.iter().filter(|p| p.number > 4).collect();
+
+        if logicals.is_empty() {
+            return Self::free_gaps(disk.starting_lba, disk.ending_lba, &primary_spans);
+        }
+
+        let first_logical_start = logicals.iter().map(|p| p.start).min().unwrap();
+        let extended_start = first_logical_start.saturating_sub(Self::MBR_EBR_SECTORS);
+        let extended_end = logicals.iter().map(|p| p.start + p.sectors).max().unwrap();
+
+        let mut top_level = primary_spans;
+        top_level.push((extended_start, extended_end));
+        top_level.sort_by_key(|&(start, _)| start);
+
+        let mut gaps = Self::free_gaps(disk.starting_lba, disk.ending_lba, &top_level);
+        let logical_spans: Vec<(u64, u64)> = logicals.iter().map(|p| (p.start, p.start + p.sectors)).collect();
+        gaps.extend(Self::free_gaps(first_logical_start, extended_end, &logical_spans));
+        gaps
+    }
+
+    /// Builds a sensible default layout inside `free`, for users who don't
+    /// want to hand-plan partitions: an ESP (UEFI) or BIOS-boot partition
+    /// (BIOS), optional swap sized by the caller, and a root partition
+    /// filling the rest. Does not mutate `self`; the caller applies the
+    /// result through the normal create flow after confirmation, since this
+    /// consumes the whole free region.
+    pub fn suggested_layout(
+        &self,
+        free: DiskSpace,
+        uefi: bool,
+        swap_sectors: Option<u64>,
+        root_filesystem: FileSystem,
+    ) -> Vec<MemPartition> {
+        let sector_size = self.disk.sector_size;
+        let mut cursor = free.start;
+        let mut remaining = free.sectors;
+        let mut number = self.next_partition_number();
+        let mut partitions = Vec::new();
+
+        let boot_bytes: u64 = if uefi { 300 * 1024 * 1024 } else { 1024 * 1024 };
+        let boot_sectors = (boot_bytes / sector_size).min(remaining);
+        if boot_sectors > 0 {
+            partitions.push(MemPartition {
+                number,
+                start: cursor,
+                sectors: boot_sectors,
+                filesystem: if uefi { FileSystem::Fat32 } else { FileSystem::Unknown },
+                label: Some(if uefi { "ESP".into() } else { "BIOSBOOT".into() }),
+                mountpoint: if uefi { Some("/boot".into()) } else { None },
+                mkfs_options: None,
+                gpt_attributes: GptAttributes::default(),
+                real: false,
+            });
+            cursor += boot_sectors;
+            remaining -= boot_sectors;
+            number += 1;
+        }
+
+        if let Some(swap_sectors) = swap_sectors {
+            let swap_sectors = swap_sectors.min(remaining);
+            if swap_sectors > 0 {
+                partitions.push(MemPartition {
+                    number,
+                    start: cursor,
+                    sectors: swap_sectors,
+                    filesystem: FileSystem::Swap,
+                    label: Some("swap".into()),
+                    mountpoint: None,
+                    mkfs_options: None,
+                    gpt_attributes: GptAttributes::default(),
+                    real: false,
+                });
+                cursor += swap_sectors;
+                remaining -= swap_sectors;
+                number += 1;
+            }
+        }
+
+        partitions.push(MemPartition {
+            number,
+            start: cursor,
+            sectors: remaining,
+            filesystem: root_filesystem,
+            label: Some("root".into()),
+            mountpoint: Some("/".into()),
+            mkfs_options: None,
+            gpt_attributes: GptAttributes::default(),
+            real: false,
+        });
+
+        partitions
+    }
+
+    /// Re-lays out a saved (sector-addressed) plan onto `free`, a free region
+    /// on a disk that may be a different size than the one the plan was
+    /// recorded against. Fixed-size partitions (everything except the
+    /// largest one, treated as the "rest" partition, typically root) keep
+    /// their exact size and are packed from the start of `free`; the "rest"
+    /// partition is scaled to consume whatever's left over. Errors if the
+    /// fixed-size partitions alone don't fit.
+    pub fn fit_plan_to_disk(plan: &[MemPartition], free: DiskSpace) -> anyhow::Result<Vec<MemPartition>> {
+        let Some((rest_index, _)) = plan.iter().enumerate().max_by_key(|(_, p)| p.sectors) else {
+            return Ok(Vec::new());
+        };
+
+        let fixed_sectors: u64 = plan
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| *i != rest_index)
+            .map(|(_, p)| p.sectors)
+            .sum();
+        anyhow::ensure!(
+            fixed_sectors < free.sectors,
+            "the fixed-size partitions in this plan ({fixed_sectors} sectors) don't fit the target disk's free space ({} sectors)",
+            free.sectors
+        );
+
+        let mut cursor = free.start;
+        let mut result = Vec::with_capacity(plan.len());
+        for (i, partition) in plan.iter().enumerate() {
+            let sectors = if i == rest_index {
+                free.sectors - fixed_sectors
+            } else {
+                partition.sectors
+            };
+            result.push(MemPartition {
+                number: partition.number,
+                start: cursor,
+                sectors,
+                filesystem: partition.filesystem,
+                label: partition.label.clone(),
+                mountpoint: partition.mountpoint.clone(),
+                mkfs_options: None,
+                gpt_attributes: partition.gpt_attributes,
+                real: partition.real,
+            });
+            cursor += sectors;
+        }
+        Ok(result)
+    }
+
+    /// Whether GRUB will need a dedicated BIOS-boot partition on this
+    /// device: legacy BIOS (not UEFI) targeting a GPT disk. UEFI doesn't use
+    /// GRUB's post-MBR embedding trick, and MBR disks have the classic gap
+    /// after the boot sector instead, so neither needs one.
+    pub fn requires_bios_boot_partition(&self, uefi: bool) -> bool {
+        !uefi && self.disk.table_type == TableType::Gpt
+    }
+
+    /// Whether `mem_table` already has the BIOS-boot partition
+    /// `suggested_layout` creates (recognized by label, since `MemPartition`
+    /// doesn't track partition type GUIDs).
+    pub fn has_bios_boot_partition(&self) -> bool {
+        self.mem_table.iter().any(|entry| {
+            matches!(entry, MemTableEntry::Partition(p) if p.label.as_deref() == Some(BIOS_BOOT_LABEL))
+        })
+    }
+
+    /// Validates that a legacy-BIOS/GPT plan has its required BIOS-boot
+    /// partition before `apply` hands the plan to `grub-install`, where a
+    /// missing one fails as an opaque "embedding area too small" error.
+    pub fn validate_bios_boot(&self, uefi: bool) -> anyhow::Result<()> {
+        if self.requires_bios_boot_partition(uefi) && !self.has_bios_boot_partition() {
+            anyhow::bail!(
+                "GRUB needs a BIOS-boot partition (GUID {BIOS_BOOT_GUID}) on this GPT disk when installing under legacy BIOS; none is planned"
+            );
+        }
+        Ok(())
+    }
+
+    /// The next partition number to assign. GPT's table is flat, so this is
+    /// just the highest existing number plus one; MBR reserves 1-4 for
+    /// primaries and only starts handing out 5+ (logical, nested in the
+    /// implicit extended partition `mbr_free_gaps` lays out) once all four
+    /// primary slots are taken.
+    fn next_partition_number(&self) -> u32 {
+        let numbers: Vec<u32> = self
+            .mem_table
+            .iter()
+            .filter_map(|entry| match entry {
+                MemTableEntry::Partition(p) => Some(p.number),
+                MemTableEntry::Free(_) => None,
+            })
+            .collect();
+
+        if self.disk.is_gpt() {
+            return numbers.iter().copied().max().map_or(1, |n| n + 1);
+        }
+
+        let primaries_used = numbers.iter().copied().filter(|&n| n <= 4).count() as u32;
+        if primaries_used < 4 {
+            return primaries_used + 1;
+        }
+        numbers.iter().copied().filter(|&n| n > 4).max().map_or(5, |n| n + 1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn disk() -> Disk {
+        Disk {
+            path: PathBuf::from("/dev/sda"),
+            model: "Test Disk".to_string(),
+            size: 1_000_000_000,
+            sector_size: 512,
+            table_type: TableType::Gpt,
+            starting_lba: 2048,
+            ending_lba: 1_953_125,
+            hybrid_mbr: false,
+        }
+    }
+
+    /// A synthetic 16 TiB, 512-byte-sector disk, to check the sector/byte
+    /// arithmetic (`fill_free_space`, `used_and_free_bytes`) holds up at a
+    /// scale well past today's typical drive, since 16 TiB / 512 bytes is
+    /// already a ~3.4e10 sector count — large enough that a bug hiding in an
+    /// `as u32` truncation or similar would actually show up.
+    fn huge_disk() -> Disk {
+        let sectors = 16u64 * 1024 * 1024 * 1024 * 1024 / 512;
+        Disk {
+            path: PathBuf::from("/dev/sda"),
+            model: "Huge Test Disk".to_string(),
+            size: sectors * 512,
+            sector_size: 512,
+            table_type: TableType::Gpt,
+            starting_lba: 34,
+            ending_lba: sectors - 34,
+            hybrid_mbr: false,
+        }
+    }
+
+    #[test]
+    fn fill_free_space_is_exact_on_a_16tib_disk() {
+        let disk = huge_disk();
+        let half = disk.ending_lba / 2;
+        let device = CompatDevice::new(
+            disk,
+            vec![MemPartition {
+                number: 1,
+                start: 34,
+                sectors: half,
+                filesystem: FileSystem::Ext4,
+                label: Some("root".into()),
+                mountpoint: Some("/".into()),
+                mkfs_options: None,
+                gpt_attributes: GptAttributes::default(),
+                real: true,
+            }],
+        );
+
+        let free = device
+            .mem_table
+            .iter()
+            .find_map(|entry| match entry {
+                MemTableEntry::Free(space) => Some(*space),
+                MemTableEntry::Partition(_) => None,
+            })
+            .expect("a trailing free region");
+
+        assert_eq!(free.start, 34 + half);
+        assert_eq!(free.sectors, device.disk.ending_lba - free.start);
+    }
+
+    #[test]
+    fn used_and_free_bytes_is_exact_on_a_16tib_disk() {
+        let disk = huge_disk();
+        let total_sectors = disk.ending_lba - disk.starting_lba;
+        let used_sectors = total_sectors / 4;
+        let device = CompatDevice::new(
+            disk,
+            vec![MemPartition {
+                number: 1,
+                start: 34,
+                sectors: used_sectors,
+                filesystem: FileSystem::Ext4,
+                label: Some("root".into()),
+                mountpoint: Some("/".into()),
+                mkfs_options: None,
+                gpt_attributes: GptAttributes::default(),
+                real: true,
+            }],
+        );
+
+        let (used, free) = device.used_and_free_bytes();
+        assert_eq!(used, used_sectors * 512);
+        assert_eq!(used + free, total_sectors * 512);
+    }
+
+    #[test]
+    fn fstab_only_includes_partitions_with_a_mountpoint() {
+        let device = CompatDevice::new(
+            disk(),
+            vec![
+                MemPartition {
+                    number: 1,
+                    start: 2048,
+                    sectors: 1_048_576,
+                    filesystem: FileSystem::Fat32,
+                    label: Some("ESP".into()),
+                    mountpoint: Some("/boot".into()),
+                    mkfs_options: None,
+                    gpt_attributes: GptAttributes::default(),
+                    real: true,
+                },
+                MemPartition {
+                    number: 2,
+                    start: 1_050_624,
+                    sectors: 2_097_152,
+                    filesystem: FileSystem::Swap,
+                    label: Some("swap".into()),
+                    mountpoint: None,
+                    mkfs_options: None,
+                    gpt_attributes: GptAttributes::default(),
+                    real: true,
+                },
+            ],
+        );
+
+        let fstab = device.generate_fstab(FstabKeyMode::Path);
+        assert!(fstab.contains("/dev/sda1\t/boot\tfat32\tdefaults\t0\t2"));
+        assert!(!fstab.contains("sda2"));
+    }
+
+    #[test]
+    fn fstab_falls_back_to_the_device_path_when_blkid_has_nothing_to_report() {
+        // `/dev/sda1` doesn't exist in the test environment, so every
+        // `blkid`-backed mode has nothing to look up and should fall back to
+        // the plain device path rather than emitting a blank column.
+        let device = CompatDevice::new(
+            disk(),
+            vec![MemPartition {
+                number: 1,
+                start: 2048,
+                sectors: 1_048_576,
+                filesystem: FileSystem::Fat32,
+                label: Some("ESP".into()),
+                mountpoint: Some("/boot".into()),
+                mkfs_options: None,
+                gpt_attributes: GptAttributes::default(),
+                real: true,
+            }],
+        );
+
+        for mode in [FstabKeyMode::Uuid, FstabKeyMode::Label, FstabKeyMode::PartUuid] {
+            assert!(device.generate_fstab(mode).contains("/dev/sda1\t/boot"));
+        }
+    }
+
+    #[test]
+    fn bios_boot_is_required_on_gpt_under_legacy_bios_only() {
+        let device = CompatDevice::new(disk(), Vec::new());
+        assert!(device.requires_bios_boot_partition(false));
+        assert!(!device.requires_bios_boot_partition(true));
+    }
+
+    #[test]
+    fn suggested_layout_bios_boot_partition_satisfies_validation() {
+        let device = CompatDevice::new(disk(), Vec::new());
+        let free = DiskSpace { start: device.disk.starting_lba, sectors: 1_000_000 };
+        let layout = device.suggested_layout(free, false, None, FileSystem::Ext4);
+        let device = CompatDevice::new(device.disk, layout);
+        assert!(device.validate_bios_boot(false).is_ok());
+    }
+
+    #[test]
+    fn missing_bios_boot_partition_is_reported() {
+        let device = CompatDevice::new(
+            disk(),
+            vec![MemPartition {
+                number: 1,
+                start: 2048,
+                sectors: 1_048_576,
+                filesystem: FileSystem::Ext4,
+                label: Some("root".into()),
+                mountpoint: Some("/".into()),
+                mkfs_options: None,
+                gpt_attributes: GptAttributes::default(),
+                real: true,
+            }],
+        );
+        let err = device.validate_bios_boot(false).unwrap_err();
+        assert!(err.to_string().contains("BIOS-boot"));
+    }
+
+    #[test]
+    fn partition_leaving_one_sector_free_still_gets_a_free_entry() {
+        // Regression for the boundary just above "no free space left": one
+        // leftover sector must still surface as a `Free` entry, not be
+        // dropped or degenerate to `sectors == 0`.
+        let mut device = CompatDevice::new(disk(), Vec::new());
+        device.mem_table = vec![MemTableEntry::Partition(MemPartition {
+            number: 1,
+            start: device.disk.starting_lba,
+            sectors: (device.disk.ending_lba - device.disk.starting_lba) - 1,
+            filesystem: FileSystem::Ext4,
+            label: Some("root".into()),
+            mountpoint: Some("/".into()),
+            mkfs_options: None,
+            gpt_attributes: GptAttributes::default(),
+            real: true,
+        })];
+        device.fill_free_space();
+
+        let free_regions: Vec<&DiskSpace> = device
+            .mem_table
+            .iter()
+            .filter_map(|entry| match entry {
+                MemTableEntry::Free(space) => Some(space),
+                MemTableEntry::Partition(_) => None,
+            })
+            .collect();
+        assert_eq!(free_regions.len(), 1);
+        assert_eq!(free_regions[0].sectors, 1);
+    }
+
+    #[test]
+    fn partition_exactly_filling_the_disk_leaves_no_free_entry() {
+        let mut device = CompatDevice::new(disk(), Vec::new());
+        device.mem_table = vec![MemTableEntry::Partition(MemPartition {
+            number: 1,
+            start: device.disk.starting_lba,
+            sectors: device.disk.ending_lba - device.disk.starting_lba,
+            filesystem: FileSystem::Ext4,
+            label: Some("root".into()),
+            mountpoint: Some("/".into()),
+            mkfs_options: None,
+            gpt_attributes: GptAttributes::default(),
+            real: true,
+        })];
+        device.fill_free_space();
+
+        assert!(device.mem_table.iter().all(|entry| !matches!(entry, MemTableEntry::Free(_))));
+    }
+
+    #[test]
+    fn partition_at_the_very_end_of_the_disk_produces_no_degenerate_trailing_free() {
+        let mut device = CompatDevice::new(disk(), Vec::new());
+        let end_sectors = 100;
+        device.mem_table = vec![MemTableEntry::Partition(MemPartition {
+            number: 1,
+            start: device.disk.ending_lba - end_sectors,
+            sectors: end_sectors,
+            filesystem: FileSystem::Ext4,
+            label: Some("tail".into()),
+            mountpoint: None,
+            mkfs_options: None,
+            gpt_attributes: GptAttributes::default(),
+            real: true,
+        })];
+        device.fill_free_space();
+
+        // Leading gap before the tail partition, no trailing zero-sector gap.
+        assert!(device.mem_table.iter().all(|entry| match entry {
+            MemTableEntry::Free(space) => space.sectors > 0,
+            MemTableEntry::Partition(_) => true,
+        }));
+        assert!(matches!(device.mem_table.last(), Some(MemTableEntry::Partition(_))));
+    }
+
+    #[test]
+    fn root_partition_gets_pass_one() {
+        let device = CompatDevice::new(
+            disk(),
+            vec![MemPartition {
+                number: 1,
+                start: 2048,
+                sectors: 1_048_576,
+                filesystem: FileSystem::Ext4,
+                label: Some("root".into()),
+                mountpoint: Some("/".into()),
+                mkfs_options: None,
+                gpt_attributes: GptAttributes::default(),
+                real: true,
+            }],
+        );
+
+        assert!(device.generate_fstab(FstabKeyMode::Path).contains("\t/\text4\tdefaults\t0\t1"));
+    }
+
+    #[test]
+    fn used_and_free_bytes_reflects_planned_partitions() {
+        let device = CompatDevice::new(
+            disk(),
+            vec![MemPartition {
+                number: 1,
+                start: 2048,
+                sectors: 1_000_000,
+                filesystem: FileSystem::Ext4,
+                label: None,
+                mountpoint: None,
+                mkfs_options: None,
+                gpt_attributes: GptAttributes::default(),
+                real: false,
+            }],
+        );
+        let (used, free) = device.used_and_free_bytes();
+        assert_eq!(used, 1_000_000 * 512);
+        assert!(free > 0);
+    }
+
+    #[test]
+    fn a_planned_partition_makes_the_device_dirty() {
+        let device = CompatDevice::new(
+            disk(),
+            vec![MemPartition {
+                number: 1,
+                start: 2048,
+                sectors: 1_000_000,
+                filesystem: FileSystem::Ext4,
+                label: None,
+                mountpoint: None,
+                mkfs_options: None,
+                gpt_attributes: GptAttributes::default(),
+                real: false,
+            }],
+        );
+        assert!(device.is_dirty());
+    }
+
+    #[test]
+    fn mark_applied_clears_the_dirty_flag() {
+        let mut device = CompatDevice::new(
+            disk(),
+            vec![MemPartition {
+                number: 1,
+                start: 2048,
+                sectors: 1_000_000,
+                filesystem: FileSystem::Ext4,
+                label: None,
+                mountpoint: None,
+                mkfs_options: None,
+                gpt_attributes: GptAttributes::default(),
+                real: false,
+            }],
+        );
+        device.mark_applied();
+        assert!(!device.is_dirty());
+    }
+
+    #[test]
+    fn partition_pinned_to_the_first_usable_lba_produces_no_underflow() {
+        // A legacy layout where an existing partition starts exactly at
+        // `starting_lba` (34 on a typical 512-byte-sector GPT disk) leaves no
+        // leading gap. `fill_free_space`'s `partition.start > cursor` guard
+        // means this never attempts `partition.start - cursor` when they're
+        // equal, so this must not panic and must produce no leading `Free`.
+        let mut device = CompatDevice::new(disk(), Vec::new());
+        device.disk.starting_lba = 34;
+        device.mem_table = vec![MemTableEntry::Partition(MemPartition {
+            number: 1,
+            start: 34,
+            sectors: 1_000_000,
+            filesystem: FileSystem::Ext4,
+            label: Some("root".into()),
+            mountpoint: Some("/".into()),
+            mkfs_options: None,
+            gpt_attributes: GptAttributes::default(),
+            real: true,
+        })];
+        device.fill_free_space();
+
+        assert!(matches!(device.mem_table.first(), Some(MemTableEntry::Partition(_))));
+    }
+
+    #[test]
+    fn partition_starting_before_the_first_usable_lba_produces_no_underflow() {
+        // An even more legacy case: a partition that starts *below*
+        // `starting_lba` altogether (e.g. an old layout predating the
+        // modern-aligned first-usable LBA). `cursor` starts at `starting_lba`
+        // and the partition's start is less than that, so the leading-gap
+        // guard (`partition.start > cursor`) skips the subtraction entirely
+        // rather than underflowing.
+        let mut device = CompatDevice::new(disk(), Vec::new());
+        device.disk.starting_lba = 34;
+        device.mem_table = vec![MemTableEntry::Partition(MemPartition {
+            number: 1,
+            start: 1,
+            sectors: 1_000_000,
+            filesystem: FileSystem::Ext4,
+            label: Some("root".into()),
+            mountpoint: Some("/".into()),
+            mkfs_options: None,
+            gpt_attributes: GptAttributes::default(),
+            real: true,
+        })];
+        device.fill_free_space();
+
+        assert!(matches!(device.mem_table.first(), Some(MemTableEntry::Partition(_))));
+    }
+
+    #[test]
+    fn parse_mkfs_options_splits_on_whitespace() {
+        assert_eq!(parse_mkfs_options("-m 0 -O metadata_csum").unwrap(), vec!["-m", "0", "-O", "metadata_csum"]);
+    }
+
+    #[test]
+    fn parse_mkfs_options_rejects_shell_metacharacters() {
+        assert!(parse_mkfs_options("-m 0; rm -rf /").is_err());
+        assert!(parse_mkfs_options("$(whoami)").is_err());
+    }
+
+    #[test]
+    fn format_partition_refuses_an_unformatted_filesystem() {
+        let device = CompatDevice::new(
+            disk(),
+            vec![MemPartition {
+                number: 1,
+                start: 2048,
+                sectors: 1_048_576,
+                filesystem: FileSystem::Unknown,
+                label: None,
+                mountpoint: None,
+                mkfs_options: None,
+                gpt_attributes: GptAttributes::default(),
+                real: true,
+            }],
+        );
+
+        assert!(device.format_partition(1).is_err());
+    }
+
+    #[test]
+    fn gpt_attributes_bits_round_trip() {
+        let attrs = GptAttributes { required: true, no_automount: true, legacy_bios_bootable: false };
+        assert_eq!(GptAttributes::from_bits(attrs.to_bits()), attrs);
+    }
+
+    #[test]
+    fn gpt_attributes_no_flags_set_round_trips_to_zero() {
+        assert_eq!(GptAttributes::default().to_bits(), 0);
+        assert_eq!(GptAttributes::from_bits(0), GptAttributes::default());
+    }
+
+    #[test]
+    fn gpt_attributes_from_bits_ignores_unmodeled_bits() {
+        // Bit 1 isn't one of the three this type models; it should be
+        // dropped rather than round-tripping into some fourth field.
+        assert_eq!(GptAttributes::from_bits(1 << 1), GptAttributes::default());
+    }
+
+    #[test]
+    fn gpt_attributes_label_lists_every_set_flag_in_a_fixed_order() {
+        let attrs = GptAttributes { required: true, no_automount: true, legacy_bios_bootable: true };
+        assert_eq!(attrs.label(), "required, no-automount, legacy BIOS bootable");
+    }
+
+    #[test]
+    fn gpt_attributes_label_is_empty_with_no_flags_set() {
+        assert_eq!(GptAttributes::default().label(), "");
+    }
+
+    fn mbr_disk() -> Disk {
+        Disk {
+            path: PathBuf::from("/dev/sdb"),
+            model: "Test MBR Disk".to_string(),
+            size: 1_000_000_000,
+            sector_size: 512,
+            table_type: TableType::Mbr,
+            starting_lba: 0,
+            ending_lba: 1_953_125,
+            hybrid_mbr: false,
+        }
+    }
+
+    fn mbr_partition(number: u32, start: u64, sectors: u64) -> MemPartition {
+        MemPartition {
+            number,
+            start,
+            sectors,
+            filesystem: FileSystem::Ext4,
+            label: None,
+            mountpoint: None,
+            mkfs_options: None,
+            gpt_attributes: GptAttributes::default(),
+            real: true,
+        }
+    }
+
+    #[test]
+    fn mbr_numbering_fills_all_four_primaries_before_going_logical() {
+        let device = CompatDevice::new(
+            mbr_disk(),
+            vec![mbr_partition(1, 0, 1000), mbr_partition(2, 1000, 1000), mbr_partition(3, 2000, 1000)],
+        );
+        assert_eq!(device.next_partition_number(), 4);
+
+        let device = CompatDevice::new(
+            mbr_disk(),
+            vec![
+                mbr_partition(1, 0, 1000),
+                mbr_partition(2, 1000, 1000),
+                mbr_partition(3, 2000, 1000),
+                mbr_partition(4, 3000, 1000),
+            ],
+        );
+        assert_eq!(device.next_partition_number(), 5);
+    }
+
+    #[test]
+    fn mbr_numbering_continues_logicals_past_five() {
+        let device = CompatDevice::new(
+            mbr_disk(),
+            vec![
+                mbr_partition(1, 0, 1000),
+                mbr_partition(2, 1000, 1000),
+                mbr_partition(3, 2000, 1000),
+                mbr_partition(4, 3000, 1000),
+                mbr_partition(5, 3100, 500),
+            ],
+        );
+        assert_eq!(device.next_partition_number(), 6);
+    }
+
+    #[test]
+    fn mbr_free_space_reports_the_gap_after_four_primaries() {
+        let device = CompatDevice::new(
+            mbr_disk(),
+            vec![
+                mbr_partition(1, 0, 1000),
+                mbr_partition(2, 1000, 1000),
+                mbr_partition(3, 2000, 1000),
+                mbr_partition(4, 3000, 1000),
+            ],
+        );
+        let free_regions: Vec<&DiskSpace> = device
+            .mem_table
+            .iter()
+            .filter_map(|entry| match entry {
+                MemTableEntry::Free(space) => Some(space),
+                MemTableEntry::Partition(_) => None,
+            })
+            .collect();
+        assert_eq!(free_regions.len(), 1);
+        assert_eq!(free_regions[0].start, 4000);
+        assert_eq!(free_regions[0].sectors, device.disk.ending_lba - 4000);
+    }
+
+    #[test]
+    fn mbr_free_space_finds_the_gap_between_logicals_inside_the_extended_partition() {
+        // Three primaries, then two logicals with a gap between them —
+        // logicals start at 4000 (leaving a 1-sector EBR reservation at
+        // 3999) and 5000, each 500 sectors, with a gap from 4500 to 5000.
+        let device = CompatDevice::new(
+            mbr_disk(),
+            vec![
+                mbr_partition(1, 0, 1000),
+                mbr_partition(2, 1000, 1000),
+                mbr_partition(3, 2000, 1000),
+                mbr_partition(5, 4000, 500),
+                mbr_partition(6, 5000, 500),
+            ],
+        );
+        let free_regions: Vec<&DiskSpace> = device
+            .mem_table
+            .iter()
+            .filter_map(|entry| match entry {
+                MemTableEntry::Free(space) => Some(space),
+                MemTableEntry::Partition(_) => None,
+            })
+            .collect();
+
+        // The gap between the third primary and the extended partition
+        // (3000..3999), the gap between the two logicals (4500..5000), and
+        // the trailing gap after the last logical.
+        assert!(free_regions.iter().any(|f| f.start == 3000 && f.sectors == 999));
+        assert!(free_regions.iter().any(|f| f.start == 4500 && f.sectors == 500));
+        assert!(free_regions.iter().any(|f| f.start == 5500));
+    }
+}
@@ -0,0 +1,343 @@
+//! Deserializes `lsblk -J` output into `Disk`s. lsblk omits fields liberally
+//! — virtio disks, loop devices, and some NVMe controllers report no model
+//! string at all, and `pttype`/`ptuuid` are absent on unpartitioned disks —
+//! so every optional field here defaults instead of unwrapping. A panic here
+//! would take down the whole partition view over perfectly normal hardware.
+
+use std::path::PathBuf;
+use std::process::Command;
+
+use anyhow::Context;
+use serde::Deserialize;
+
+use crate::command::CommandExt;
+
+use super::gpt::{GptReader, UsableRangeSource};
+use super::{Device, Disk, FileSystem, GptAttributes, MemPartition, TableType};
+
+#[derive(Debug, Deserialize)]
+pub struct RawDevice {
+    pub name: String,
+    pub model: Option<String>,
+    pub size: u64,
+    #[serde(rename = "log-sec")]
+    pub log_sec: Option<u64>,
+    pub pttype: Option<String>,
+    pub ptuuid: Option<String>,
+}
+
+impl RawDevice {
+    /// Converts an `lsblk` row into a `Disk`, defaulting any field it may
+    /// have omitted rather than panicking on missing hardware metadata.
+    /// `starting_lba`/`ending_lba` are left at `0`; the caller fills those in
+    /// once it knows the device's usable-LBA range.
+    pub fn into_disk(self) -> Disk {
+        let model = self
+            .model
+            .map(|model| model.trim().to_string())
+            .filter(|model| !model.is_empty())
+            .unwrap_or_else(|| "Unknown model".to_string());
+        let sector_size = self.log_sec.unwrap_or(512);
+        let table_type = match self.pttype.as_deref() {
+            Some("gpt") => TableType::Gpt,
+            Some("dos") => TableType::Mbr,
+            _ => TableType::None,
+        };
+
+        Disk {
+            path: PathBuf::from(format!("/dev/{}", self.name)),
+            model,
+            size: self.size,
+            sector_size,
+            table_type,
+            starting_lba: 0,
+            ending_lba: 0,
+            hybrid_mbr: false,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct RawLsblk {
+    blockdevices: Vec<RawDevice>,
+}
+
+/// Runs `lsblk -J` and builds a `Device` for each disk, reading its real boot
+/// sector to detect hybrid MBRs. `parse_devices` builds devices from the JSON
+/// alone, without this boot-sector read or the GPT header and partition
+/// reads, for tests that only have a JSON fixture.
+pub fn get_devices() -> anyhow::Result<Vec<Device>> {
+    let json = Command::new("lsblk")
+        .args(["-J", "-b", "-o", "NAME,MODEL,SIZE,LOG-SEC,PTTYPE,PTUUID"])
+        .read()?;
+    let raw: RawLsblk = serde_json::from_str(&json).context("failed to parse lsblk output")?;
+    Ok(raw
+        .blockdevices
+        .into_iter()
+        .map(|device| build_real_device(device, &GptReader))
+        .collect())
+}
+
+/// Builds a `Device` from a real `lsblk` row, narrowing the whole-disk
+/// approximation from `fill_usable_range` down to the real usable range for
+/// GPT disks via `source`, and reading the disk's existing partitions so it
+/// doesn't show up as one big free region. A failed header read is logged
+/// and treated as "no partitions found, use the whole disk" rather than
+/// dropping the device entirely — one unreadable header shouldn't take out
+/// the rest of the partition view.
+fn build_real_device(raw: RawDevice, source: &dyn UsableRangeSource) -> Device {
+    let mut disk = fill_usable_range(raw.into_disk());
+    let mut partitions = Vec::new();
+    if disk.table_type == TableType::Gpt {
+        match source.usable_range(&disk.path) {
+            Ok((starting_lba, ending_lba)) => {
+                disk.starting_lba = starting_lba;
+                disk.ending_lba = ending_lba;
+            }
+            Err(err) => log::warn!(
+                "{}: failed to read GPT header, treating the whole disk as usable: {err:#}",
+                disk.path.display()
+            ),
+        }
+        match source.read_partitions(&disk.path) {
+            Ok(found) => partitions = found,
+            Err(err) => log::warn!(
+                "{}: failed to read GPT partition entries, treating the disk as empty: {err:#}",
+                disk.path.display()
+            ),
+        }
+    }
+    let mut boot_sector = Vec::new();
+    if let Ok(file) = std::fs::File::open(&disk.path) {
+        use std::io::Read;
+        let _ = file.take(512).read_to_end(&mut boot_sector);
+    }
+    if disk.table_type == TableType::Mbr {
+        partitions = parse_mbr_partitions(&boot_sector);
+    }
+    Device::new_from(disk, &boot_sector, partitions)
+}
+
+/// Hand-parses the four primary entries of an MBR partition table directly
+/// out of the boot sector, since there's no GPT-style crate support for MBR
+/// in this tree. Entries start at byte 446, are 16 bytes each, with the
+/// partition-type byte at offset 4 and little-endian `u32`s for LBA-start
+/// (offset 8) and sector count (offset 12) — the same layout
+/// `detect_mbr_kind` already reads the type byte from.
+///
+/// Only reads primary partitions. An extended-partition entry (type `0x05`
+/// or `0x0F`) marks the start of a logical-partition chain that lives in
+/// further EBRs beyond this one sector — reading those isn't attempted here
+/// and is a known gap, not a silent guess.
+fn parse_mbr_partitions(boot_sector: &[u8]) -> Vec<MemPartition> {
+    const TABLE_OFFSET: usize = 446;
+    const ENTRY_SIZE: usize = 16;
+    const EXTENDED_TYPES: [u8; 2] = [0x05, 0x0F];
+
+    if boot_sector.len() < TABLE_OFFSET + 4 * ENTRY_SIZE {
+        return Vec::new();
+    }
+
+    (0..4u32)
+        .filter_map(|i| {
+            let entry = &boot_sector[TABLE_OFFSET + i as usize * ENTRY_SIZE..];
+            let partition_type = entry[4];
+            if partition_type == 0x00 || EXTENDED_TYPES.contains(&partition_type) {
+                return None;
+            }
+
+            let start = u32::from_le_bytes(entry[8..12].try_into().unwrap()) as u64;
+            let sectors = u32::from_le_bytes(entry[12..16].try_into().unwrap()) as u64;
+
+            Some(MemPartition {
+                number: i + 1,
+                start,
+                sectors,
+                filesystem: FileSystem::Unknown,
+                label: None,
+                mountpoint: None,
+                mkfs_options: None,
+                gpt_attributes: GptAttributes::default(),
+                real: true,
+            })
+        })
+        .collect()
+}
+
+/// Parses `lsblk -J` output into `Device`s without touching any real boot
+/// sector, so tests can exercise device construction — GPT, MBR, no-table,
+/// and devices with missing fields — from static JSON fixtures instead of
+/// real hardware. Hybrid-MBR detection always comes back `false` here since
+/// that needs the actual boot sector; `get_devices` overlays it for real
+/// disks.
+pub fn parse_devices(json: &str) -> anyhow::Result<Vec<Device>> {
+    let raw: RawLsblk = serde_json::from_str(json).context("failed to parse lsblk output")?;
+    Ok(raw
+        .blockdevices
+        .into_iter()
+        .map(|device| Device::new_from(fill_usable_range(device.into_disk()), &[], Vec::new()))
+        .collect())
+}
+
+/// `lsblk` doesn't report first/last-usable-LBA directly, so approximate the
+/// whole disk as usable. Real GPT/MBR header parsing narrows this once it
+/// runs against the actual device.
+fn fill_usable_range(mut disk: Disk) -> Disk {
+    disk.starting_lba = 0;
+    disk.ending_lba = if disk.sector_size > 0 { disk.size / disk.sector_size } else { 0 };
+    disk
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tui::data::partition::{CompatDevice, MemTableEntry};
+
+    #[test]
+    fn null_model_falls_back_instead_of_panicking() {
+        let raw: RawDevice = serde_json::from_str(
+            r#"{"name": "vda", "model": null, "size": 1073741824, "log-sec": null, "pttype": "gpt", "ptuuid": null}"#,
+        )
+        .unwrap();
+        let disk = raw.into_disk();
+        assert_eq!(disk.model, "Unknown model");
+        assert_eq!(disk.path, PathBuf::from("/dev/vda"));
+        assert_eq!(disk.sector_size, 512);
+        assert_eq!(disk.table_type, TableType::Gpt);
+    }
+
+    #[test]
+    fn blank_model_also_falls_back() {
+        let raw: RawDevice = serde_json::from_str(
+            r#"{"name": "loop0", "model": "  ", "size": 0, "log-sec": 512, "pttype": null, "ptuuid": null}"#,
+        )
+        .unwrap();
+        assert_eq!(raw.into_disk().model, "Unknown model");
+    }
+
+    #[test]
+    fn gpt_disk_parses_as_compatible() {
+        let devices = parse_devices(
+            r#"{"blockdevices": [{"name": "sda", "model": "Samsung SSD", "size": 500107862016, "log-sec": 512, "pttype": "gpt", "ptuuid": "abc-123"}]}"#,
+        )
+        .unwrap();
+        assert_eq!(devices.len(), 1);
+        assert!(matches!(devices[0], Device::Compatible(_)));
+        assert_eq!(devices[0].disk().table_type, TableType::Gpt);
+    }
+
+    #[test]
+    fn mbr_disk_parses_as_compatible() {
+        let devices = parse_devices(
+            r#"{"blockdevices": [{"name": "sdb", "model": "Old Drive", "size": 128849018880, "log-sec": 512, "pttype": "dos", "ptuuid": null}]}"#,
+        )
+        .unwrap();
+        assert_eq!(devices[0].disk().table_type, TableType::Mbr);
+        assert!(matches!(devices[0], Device::Compatible(_)));
+    }
+
+    #[test]
+    fn no_table_disk_is_incompatible() {
+        let devices = parse_devices(
+            r#"{"blockdevices": [{"name": "sdc", "model": "Blank Disk", "size": 1000000000, "log-sec": 512, "pttype": null, "ptuuid": null}]}"#,
+        )
+        .unwrap();
+        assert!(matches!(devices[0], Device::Incompatible(_)));
+        assert_eq!(devices[0].disk().table_type, TableType::None);
+    }
+
+    struct FakeUsableRange {
+        range: (u64, u64),
+    }
+
+    impl UsableRangeSource for FakeUsableRange {
+        fn usable_range(&self, _path: &std::path::Path) -> anyhow::Result<(u64, u64)> {
+            Ok(self.range)
+        }
+    }
+
+    #[test]
+    fn build_real_device_uses_the_injected_gpt_range() {
+        let raw: RawDevice = serde_json::from_str(
+            r#"{"name": "sda", "model": "Test Disk", "size": 1073741824, "log-sec": 512, "pttype": "gpt", "ptuuid": null}"#,
+        )
+        .unwrap();
+        let source = FakeUsableRange { range: (34, 2097118) };
+        let device = build_real_device(raw, &source);
+        assert_eq!(device.disk().starting_lba, 34);
+        assert_eq!(device.disk().ending_lba, 2097118);
+    }
+
+    struct FakeExistingPartitions {
+        partitions: Vec<MemPartition>,
+    }
+
+    impl UsableRangeSource for FakeExistingPartitions {
+        fn usable_range(&self, _path: &std::path::Path) -> anyhow::Result<(u64, u64)> {
+            Ok((34, 2097118))
+        }
+
+        fn read_partitions(&self, _path: &std::path::Path) -> anyhow::Result<Vec<MemPartition>> {
+            Ok(self.partitions.clone())
+        }
+    }
+
+    #[test]
+    fn build_real_device_threads_real_gpt_partitions_through() {
+        let raw: RawDevice = serde_json::from_str(
+            r#"{"name": "sda", "model": "Test Disk", "size": 1073741824, "log-sec": 512, "pttype": "gpt", "ptuuid": null}"#,
+        )
+        .unwrap();
+        let source = FakeExistingPartitions {
+            partitions: vec![MemPartition {
+                number: 1,
+                start: 2048,
+                sectors: 1000000,
+                filesystem: FileSystem::Ext4,
+                label: None,
+                mountpoint: None,
+                mkfs_options: None,
+                gpt_attributes: GptAttributes::default(),
+                real: true,
+            }],
+        };
+        let device = build_real_device(raw, &source);
+        let CompatDevice { mem_table, .. } = match device {
+            Device::Compatible(compat) => compat,
+            Device::Incompatible(_) => panic!("expected a compatible device"),
+        };
+        assert!(mem_table.iter().any(|entry| matches!(
+            entry,
+            MemTableEntry::Partition(partition) if partition.number == 1 && partition.real
+        )));
+    }
+
+    struct FailingUsableRange;
+
+    impl UsableRangeSource for FailingUsableRange {
+        fn usable_range(&self, _path: &std::path::Path) -> anyhow::Result<(u64, u64)> {
+            anyhow::bail!("no such device")
+        }
+    }
+
+    #[test]
+    fn build_real_device_falls_back_to_whole_disk_on_read_failure() {
+        let raw: RawDevice = serde_json::from_str(
+            r#"{"name": "sda", "model": "Test Disk", "size": 1073741824, "log-sec": 512, "pttype": "gpt", "ptuuid": null}"#,
+        )
+        .unwrap();
+        let device = build_real_device(raw, &FailingUsableRange);
+        assert_eq!(device.disk().starting_lba, 0);
+        assert_eq!(device.disk().ending_lba, 1073741824 / 512);
+    }
+
+    #[test]
+    fn missing_fields_still_parse() {
+        let devices = parse_devices(
+            r#"{"blockdevices": [{"name": "vda", "model": null, "size": 1073741824, "log-sec": null, "pttype": "gpt", "ptuuid": null, "children": [{"name": "vda1"}]}]}"#,
+        )
+        .unwrap();
+        assert_eq!(devices[0].disk().model, "Unknown model");
+        assert_eq!(devices[0].disk().sector_size, 512);
+    }
+}
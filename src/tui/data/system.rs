@@ -0,0 +1,44 @@
+//! Small system-information probes that don't belong to any one view.
+
+use anyhow::{Context, Result};
+
+/// Total system RAM in bytes, read from `/proc/meminfo`'s `MemTotal:` line
+/// (reported in kB there). Used to default swap size in the suggested-layout
+/// generator.
+pub fn get_total_ram() -> Result<u64> {
+    let contents = std::fs::read_to_string("/proc/meminfo").context("failed to read /proc/meminfo")?;
+    parse_mem_total(&contents)
+}
+
+fn parse_mem_total(meminfo: &str) -> Result<u64> {
+    let line = meminfo
+        .lines()
+        .find(|line| line.starts_with("MemTotal:"))
+        .context("no MemTotal line in /proc/meminfo")?;
+
+    let kb: u64 = line
+        .trim_start_matches("MemTotal:")
+        .trim()
+        .trim_end_matches("kB")
+        .trim()
+        .parse()
+        .with_context(|| format!("could not parse MemTotal line: '{line}'"))?;
+
+    Ok(kb * 1024)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_mem_total_line() {
+        let meminfo = "MemTotal:       16384000 kB\nMemFree:        1000000 kB\n";
+        assert_eq!(parse_mem_total(meminfo).unwrap(), 16384000 * 1024);
+    }
+
+    #[test]
+    fn errors_without_mem_total_line() {
+        assert!(parse_mem_total("MemFree: 100 kB\n").is_err());
+    }
+}
@@ -0,0 +1,263 @@
+pub mod all_partitions;
+pub mod console_font;
+pub mod error;
+pub mod keyboard;
+pub mod locale;
+pub mod main;
+pub mod partition;
+pub mod summary;
+pub mod timeline;
+pub mod timezone;
+
+use anyhow::Result;
+use crossterm::event::{Event, KeyCode, KeyEventKind, MouseEvent};
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    text::Line,
+    widgets::Paragraph,
+    Frame,
+};
+
+use crate::config::Config;
+use crate::tui::widgets::menu::{Menu, MenuArgs};
+
+/// Model keybinding hints for views that navigate a plain list.
+pub const NAVIGATION_TIP: &str = "↑/↓ j/k move · Enter select · Esc back";
+/// Model keybinding hints for views with a text search/filter box.
+pub const SEARCH_TIP: &str = "↑/↓ j/k move · / search · Enter select · Esc back";
+/// Model keybinding hints for list views that support starring favorites.
+pub const FAVORITES_TIP: &str = "↑/↓ j/k move · * star · Enter select · Esc back";
+
+/// Below this height, giving up a row to the keybinding hint line is no
+/// longer worth it — a short terminal (serial console, a small VM window)
+/// needs every row it has for the actual list/table. `split_body_and_hint`
+/// drops the hint in that case rather than cramping the body further; the
+/// search bar `SearchableMenu`/`Input` draw is unaffected either way, since
+/// they lay themselves out inside whatever body they're given.
+pub const COMPACT_HEIGHT: u16 = 22;
+
+/// Splits off the bottom line of `area` for a compact keybinding hint,
+/// returning `(body, hint_line)`. Lighter-weight than the full `?` help
+/// overlay and always visible, unlike the search bar it stands in for on
+/// views without search. Below `COMPACT_HEIGHT`, skips the split entirely
+/// and hands the whole area to `body`; `hint` is a harmless zero-height
+/// `Rect` in that case, so callers don't need their own branch.
+pub fn split_body_and_hint(area: Rect) -> (Rect, Rect) {
+    if area.height < COMPACT_HEIGHT {
+        return (area, Rect { height: 0, ..area });
+    }
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(0), Constraint::Length(1)])
+        .split(area);
+    (chunks[0], chunks[1])
+}
+
+/// Renders a dim, centered keybinding hint line, as produced by
+/// `split_body_and_hint`.
+pub fn render_keybinding_hint(frame: &mut Frame, area: Rect, tip: &str) {
+    frame.render_widget(
+        Paragraph::new(Line::from(tip)).alignment(Alignment::Center),
+        area,
+    );
+}
+
+/// Below this width/height, layouts (which assume room for headers, a
+/// search bar, and at least a few list rows) render garbled or panic on
+/// zero-height splits. `render_view` short-circuits to a friendly message
+/// instead.
+pub const MIN_WIDTH: u16 = 60;
+pub const MIN_HEIGHT: u16 = 18;
+
+/// What should happen to the view route stack after an event is handled.
+pub enum Msg {
+    /// Push a new view on top of the stack (e.g. entering a sub-menu).
+    Push(Box<dyn View>),
+    /// Pop the current view, returning to whatever is beneath it.
+    Pop,
+    /// Tear the whole application down.
+    Close(Operation),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operation {
+    Quit,
+}
+
+/// A single screen in the installer. The route stack in `app::guide` holds a
+/// `Vec<Box<dyn View>>` and only renders/dispatches events to the top one.
+///
+/// **Focus-dispatch contract:** if a view owns an `Input` that's currently
+/// open (a text-entry popup, an inline field), `on_event` must feed every key
+/// event to that `Input::on_event` first and return before falling through
+/// to the view's own single-key bindings. Single letters like `q`, `j`, `k`,
+/// `g`, `G` overlap with common navigation/quit keys — routing them to
+/// command matching while an `Input` has focus means the user can't type
+/// those letters at all. `PartitionView`'s jump-to-fit and alias prompts
+/// follow this pattern: check the popup's `show_*` flag and dispatch to it
+/// unconditionally before matching anything else.
+pub trait View {
+    fn render(&mut self, frame: &mut Frame, config: &Config);
+    fn on_event(&mut self, event: Event, config: &mut Config) -> Result<Option<Msg>>;
+}
+
+/// Render the top of the route stack, applying any cross-cutting behavior
+/// (currently the minimum-terminal-size guard).
+pub fn render_view(view: &mut dyn View, frame: &mut Frame, config: &Config) {
+    let area = frame.size();
+    if area.width < MIN_WIDTH || area.height < MIN_HEIGHT {
+        let message = format!(
+            "Please enlarge your terminal (min {MIN_WIDTH}x{MIN_HEIGHT}, currently {}x{})",
+            area.width, area.height
+        );
+        frame.render_widget(Paragraph::new(Line::from(message)).alignment(Alignment::Center), area);
+        return;
+    }
+    view.render(frame, config);
+}
+
+/// The key used to unset an optional single-select field back to its
+/// default/unset state. Shared so every single-select view (keyboard layout,
+/// timezone, ...) treats "clear" the same way instead of each picking its own
+/// key.
+pub fn is_clear_key(event: &Event) -> bool {
+    matches!(
+        event,
+        Event::Key(key)
+            if key.kind == KeyEventKind::Press
+                && matches!(key.code, KeyCode::Delete | KeyCode::Char('x'))
+    )
+}
+
+/// Which pane of a `MasterDetail` has keyboard focus.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Pane {
+    Left,
+    Right,
+}
+
+/// Two side-by-side `Menu`s (e.g. region/city, device/partition) with
+/// Tab/`h`/`l` focus switching and the focused pane marked in its title.
+/// Extracted so views that need this split don't each hand-roll their own
+/// `Pane`/focus-switch boilerplate.
+pub struct MasterDetail {
+    pub left: Menu,
+    pub right: Menu,
+    pub focus: Pane,
+    left_title: String,
+    right_title: String,
+}
+
+impl MasterDetail {
+    pub fn new(left_title: impl Into<String>, left_items: Vec<String>, right_title: impl Into<String>, right_items: Vec<String>) -> Self {
+        let left_title = left_title.into();
+        let right_title = right_title.into();
+        let left = Menu::new(left_items, MenuArgs::default().title(Self::titled(&left_title, true)));
+        let right = Menu::new(right_items, MenuArgs::default().title(Self::titled(&right_title, false)));
+        Self { left, right, focus: Pane::Left, left_title, right_title }
+    }
+
+    fn titled(title: &str, focused: bool) -> String {
+        if focused { format!("> {title}") } else { title.to_string() }
+    }
+
+    pub fn set_focus(&mut self, focus: Pane) {
+        self.focus = focus;
+        self.left.set_title(Self::titled(&self.left_title, focus == Pane::Left));
+        self.right.set_title(Self::titled(&self.right_title, focus == Pane::Right));
+    }
+
+    pub fn switch(&mut self) {
+        let next = match self.focus {
+            Pane::Left => Pane::Right,
+            Pane::Right => Pane::Left,
+        };
+        self.set_focus(next);
+    }
+
+    pub fn focused(&mut self) -> &mut Menu {
+        match self.focus {
+            Pane::Left => &mut self.left,
+            Pane::Right => &mut self.right,
+        }
+    }
+
+    /// Moves the selection within the focused pane, or switches focus.
+    /// Returns whether the key was consumed, so callers can fall through to
+    /// their own bindings (Enter, Esc, ...) otherwise.
+    pub fn handle_key(&mut self, key: KeyCode) -> bool {
+        match key {
+            KeyCode::Down | KeyCode::Char('j') => {
+                self.focused().selectable.select_next_item();
+                true
+            }
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.focused().selectable.select_prev_item();
+                true
+            }
+            KeyCode::Tab => {
+                self.switch();
+                true
+            }
+            KeyCode::Char('h') => {
+                self.set_focus(Pane::Left);
+                true
+            }
+            KeyCode::Char('l') => {
+                self.set_focus(Pane::Right);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Routes a mouse event to whichever pane it landed in, switching focus
+    /// to that pane on a click so the keyboard shortcuts immediately after
+    /// (Enter, the clear key, ...) act on what was just clicked. Returns the
+    /// pane that consumed the event, so callers like `Locale` (which needs
+    /// to resync its right pane whenever the left one's selection moves)
+    /// know which side actually changed instead of just that "something"
+    /// did.
+    pub fn handle_mouse(&mut self, event: &MouseEvent) -> Option<Pane> {
+        if self.left.handle_mouse(event) {
+            self.set_focus(Pane::Left);
+            return Some(Pane::Left);
+        }
+        if self.right.handle_mouse(event) {
+            self.set_focus(Pane::Right);
+            return Some(Pane::Right);
+        }
+        None
+    }
+
+    pub fn render(&mut self, frame: &mut Frame, area: Rect) {
+        let chunks = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(40), Constraint::Percentage(60)])
+            .split(area);
+        self.left.render_with_focus(frame, chunks[0], self.focus == Pane::Left);
+        self.right.render_with_focus(frame, chunks[1], self.focus == Pane::Right);
+    }
+}
+
+/// Carve a `width x height` rectangle out of the center of `area`, used for
+/// modal popups.
+pub fn centered_rect(width: u16, height: u16, area: Rect) -> Rect {
+    let popup_layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(area.height.saturating_sub(height) / 2),
+            Constraint::Length(height.min(area.height)),
+            Constraint::Min(0),
+        ])
+        .split(area);
+
+    Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Length(area.width.saturating_sub(width) / 2),
+            Constraint::Length(width.min(area.width)),
+            Constraint::Min(0),
+        ])
+        .split(popup_layout[1])[1]
+}
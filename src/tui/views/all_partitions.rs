@@ -0,0 +1,96 @@
+//! Read-only overview of every partition on every detected device, for
+//! checking the whole plan at a glance before committing to `A` apply on
+//! each device individually. A snapshot of `PartitionView`'s device list
+//! taken when the view was pushed, like `Timeline` is a snapshot of a
+//! `PhaseTimeline` — it doesn't reflect edits made after that.
+
+use anyhow::Result;
+use crossterm::event::{Event, KeyCode, KeyEventKind};
+use ratatui::{
+    layout::Constraint,
+    widgets::{Block, Borders, Row, Table},
+    Frame,
+};
+
+use crate::config::Config;
+use crate::tui::data::partition::{format_size, Device, MemTableEntry};
+use crate::tui::views::{render_keybinding_hint, split_body_and_hint, Msg, View};
+
+/// Keybinding hint: Esc is the only key this read-only view handles.
+const TIP: &str = "Esc back";
+
+/// Read-only table of every `MemTableEntry` across every `Device::Compatible`
+/// device, with a device-path column so rows from different disks aren't
+/// mistaken for one another. `Device::Incompatible` devices contribute
+/// nothing — they have no `mem_table` to list.
+pub struct AllPartitions {
+    devices: Vec<Device>,
+}
+
+impl AllPartitions {
+    pub fn new(devices: Vec<Device>) -> Self {
+        Self { devices }
+    }
+
+    fn rows(&self) -> Vec<Row> {
+        self.devices
+            .iter()
+            .filter_map(|device| match device {
+                Device::Compatible(dev) => Some(dev),
+                Device::Incompatible(_) => None,
+            })
+            .flat_map(|dev| {
+                let path = dev.disk().path.display().to_string();
+                let sector_size = dev.disk().sector_size;
+                dev.mem_table.iter().map(move |entry| {
+                    let (number, start, sectors, filesystem, mountpoint) = match entry {
+                        MemTableEntry::Partition(partition) => (
+                            partition.number.to_string(),
+                            partition.start,
+                            partition.sectors,
+                            partition.filesystem.as_ref().to_string(),
+                            partition.mountpoint.clone().unwrap_or_default(),
+                        ),
+                        MemTableEntry::Free(space) => {
+                            ("-".to_string(), space.start, space.sectors, "free".to_string(), String::new())
+                        }
+                    };
+                    Row::new(vec![
+                        path.clone(),
+                        number,
+                        format_size(sectors * sector_size),
+                        filesystem,
+                        mountpoint,
+                    ])
+                })
+            })
+            .collect()
+    }
+}
+
+impl View for AllPartitions {
+    fn render(&mut self, frame: &mut Frame, _config: &Config) {
+        let (body, hint) = split_body_and_hint(frame.size());
+        let table = Table::new(self.rows())
+            .header(Row::new(vec!["Device", "#", "Size", "Filesystem", "Mountpoint"]))
+            .block(Block::default().borders(Borders::ALL).title("All partitions"))
+            .widths(&[
+                Constraint::Percentage(35),
+                Constraint::Percentage(10),
+                Constraint::Percentage(15),
+                Constraint::Percentage(20),
+                Constraint::Percentage(20),
+            ]);
+        frame.render_widget(table, body);
+        render_keybinding_hint(frame, hint, TIP);
+    }
+
+    fn on_event(&mut self, event: Event, _config: &mut Config) -> Result<Option<Msg>> {
+        if let Event::Key(key) = event {
+            if key.kind == KeyEventKind::Press && key.code == KeyCode::Esc {
+                return Ok(Some(Msg::Pop));
+            }
+        }
+        Ok(None)
+    }
+}
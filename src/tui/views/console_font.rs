@@ -0,0 +1,140 @@
+use std::process::Command;
+
+use anyhow::Result;
+use crossterm::event::{Event, KeyCode, KeyEventKind};
+use ratatui::Frame;
+
+use crate::command::CommandExt;
+use crate::config::Config;
+use crate::favorites::Favorites;
+use crate::tui::views::{is_clear_key, render_keybinding_hint, split_body_and_hint, Msg, View, FAVORITES_TIP};
+use crate::tui::widgets::menu::{Menu, MenuArgs};
+
+/// Key `Favorites` entries for this menu are filed under.
+const MENU_NAME: &str = "console_font";
+
+/// Where `setfont`-compatible console fonts live on an Arch (and Artix)
+/// system, alongside `keyboard::KEYMAP_DIR` for keymaps.
+const CONSOLEFONT_DIR: &str = "/usr/share/kbd/consolefonts";
+
+/// Lists the console fonts available under `CONSOLEFONT_DIR`, stripping the
+/// `.psfu.gz`/`.psf.gz` extension `setfont` doesn't want in its argument.
+/// Returns an empty list (not an error) if the directory can't be found or
+/// read — e.g. running outside a real Arch/Artix environment — so the
+/// picker just shows nothing selectable instead of failing the view.
+pub fn get_console_fonts() -> Vec<String> {
+    let output = match Command::new("find").args([CONSOLEFONT_DIR, "-iname", "*.psf*.gz", "-printf", "%f\n"]).read() {
+        Ok(output) => output,
+        Err(err) => {
+            log::warn!("failed to list console fonts: {err:#}");
+            return Vec::new();
+        }
+    };
+    let mut fonts: Vec<String> = output.lines().filter_map(strip_font_extension).map(str::to_string).collect();
+    fonts.sort();
+    fonts.dedup();
+    fonts
+}
+
+fn strip_font_extension(filename: &str) -> Option<&str> {
+    filename.strip_suffix(".psfu.gz").or_else(|| filename.strip_suffix(".psf.gz"))
+}
+
+/// Optional console-font picker, a sibling of `Keyboard` for the rest of the
+/// console localization story. Left unset by default (`config.console_font
+/// == None`) — `setfont` just keeps whatever the kernel/initramfs already
+/// loaded, which is fine for Latin-script layouts.
+pub struct ConsoleFont {
+    menu: Menu,
+    favorites: Favorites,
+}
+
+impl ConsoleFont {
+    pub fn new(config: &Config) -> Self {
+        let favorites = Favorites::load();
+        let items = favorites.pin(MENU_NAME, get_console_fonts());
+        let mut menu = Menu::new(items.clone(), MenuArgs::default().title("Console font".into()));
+        if let Some(current) = &config.console_font {
+            if let Some(index) = items.iter().position(|item| item == current) {
+                menu.select(Some(index));
+            }
+        }
+        Self { menu, favorites }
+    }
+
+    /// Stars/un-stars the selected font and re-pins the list so the change
+    /// is reflected immediately.
+    fn toggle_favorite(&mut self) {
+        let Some(current) = self.menu.current_item().cloned() else {
+            return;
+        };
+        self.favorites.toggle(MENU_NAME, &current);
+        let _ = self.favorites.save();
+        let items = self.favorites.pin(MENU_NAME, get_console_fonts());
+        self.menu.update_items(items.clone());
+        self.menu.select(items.iter().position(|item| *item == current));
+    }
+}
+
+impl View for ConsoleFont {
+    fn render(&mut self, frame: &mut Frame, _config: &Config) {
+        let (body, hint) = split_body_and_hint(frame.size());
+        self.menu.render(frame, body);
+        render_keybinding_hint(frame, hint, FAVORITES_TIP);
+    }
+
+    fn on_event(&mut self, event: Event, config: &mut Config) -> Result<Option<Msg>> {
+        if is_clear_key(&event) {
+            config.console_font = None;
+            self.menu.select(None);
+            return Ok(None);
+        }
+
+        if let Event::Mouse(mouse) = &event {
+            self.menu.handle_mouse(mouse);
+            return Ok(None);
+        }
+
+        if let Event::Key(key) = event {
+            if key.kind != KeyEventKind::Press {
+                return Ok(None);
+            }
+            match key.code {
+                KeyCode::Down | KeyCode::Char('j') => self.menu.selectable.select_next_item(),
+                KeyCode::Up | KeyCode::Char('k') => self.menu.selectable.select_prev_item(),
+                KeyCode::Char('*') => self.toggle_favorite(),
+                KeyCode::Enter => {
+                    if let Some(font) = self.menu.current_item() {
+                        config.console_font = Some(font.clone());
+                    }
+                    return Ok(Some(Msg::Pop));
+                }
+                KeyCode::Esc => return Ok(Some(Msg::Pop)),
+                // Type-ahead jump, same as `Keyboard`/`Timezone`. Any command
+                // letter above (j, k, *) is matched first, so it never
+                // reaches here.
+                KeyCode::Char(c) => {
+                    self.menu.jump_to_prefix(c);
+                }
+                _ => {}
+            }
+        }
+        Ok(None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn strip_font_extension_handles_both_compressed_suffixes() {
+        assert_eq!(strip_font_extension("ter-116n.psfu.gz"), Some("ter-116n"));
+        assert_eq!(strip_font_extension("lat2-16.psf.gz"), Some("lat2-16"));
+    }
+
+    #[test]
+    fn strip_font_extension_is_none_for_an_unrecognized_file() {
+        assert_eq!(strip_font_extension("README"), None);
+    }
+}
@@ -0,0 +1,49 @@
+//! In-TUI display for a recoverable error, so a mid-session failure (a
+//! failed command, a data-fetch error) doesn't tear down the whole
+//! installer the way propagating it out of `guide` used to.
+
+use anyhow::Result;
+use crossterm::event::{Event, KeyCode, KeyEventKind};
+use ratatui::{
+    widgets::{Block, Borders, Paragraph, Wrap},
+    Frame,
+};
+
+use crate::config::Config;
+use crate::tui::views::{render_keybinding_hint, split_body_and_hint, Msg, View};
+
+const ERROR_TIP: &str = "any key: dismiss";
+
+/// Pushed onto the route stack in place of tearing the app down whenever a
+/// view's `on_event` returns `Err`. Shows the error's full `{:#}` chain —
+/// the same format `CommandExt`'s errors and `run_step_with_retry`'s retry
+/// prompt already use — and pops itself on the next keypress, returning to
+/// whatever view was underneath when the error happened.
+pub struct ErrorView {
+    message: String,
+}
+
+impl ErrorView {
+    pub fn new(error: &anyhow::Error) -> Self {
+        Self { message: format!("{error:#}") }
+    }
+}
+
+impl View for ErrorView {
+    fn render(&mut self, frame: &mut Frame, _config: &Config) {
+        let (body, hint) = split_body_and_hint(frame.size());
+        let block = Block::default().borders(Borders::ALL).title("Error");
+        let paragraph = Paragraph::new(self.message.as_str()).block(block).wrap(Wrap { trim: false });
+        frame.render_widget(paragraph, body);
+        render_keybinding_hint(frame, hint, ERROR_TIP);
+    }
+
+    fn on_event(&mut self, event: Event, _config: &mut Config) -> Result<Option<Msg>> {
+        if let Event::Key(key) = event {
+            if key.kind == KeyEventKind::Press {
+                return Ok(Some(Msg::Pop));
+            }
+        }
+        Ok(None)
+    }
+}
@@ -0,0 +1,134 @@
+use std::path::PathBuf;
+use std::process::Command;
+
+use anyhow::Result;
+use crossterm::event::{Event, KeyCode, KeyEventKind};
+use ratatui::Frame;
+
+use crate::command::CommandExt;
+use crate::config::Config;
+use crate::favorites::Favorites;
+use crate::tui::views::{is_clear_key, render_keybinding_hint, split_body_and_hint, Msg, View, FAVORITES_TIP};
+use crate::tui::widgets::menu::{Menu, MenuArgs};
+
+/// Key `Favorites` entries for this menu are filed under.
+const MENU_NAME: &str = "keyboard";
+
+/// Where `loadkeys`-compatible keymaps live on an Arch (and Artix) system.
+const KEYMAP_DIR: &str = "/usr/share/kbd/keymaps";
+
+/// Hard-coded for now; the real layout list will come from scanning the
+/// system's keymap directory once that data source lands.
+fn layouts() -> Vec<String> {
+    vec!["us".into(), "de".into(), "fr".into(), "colemak".into()]
+}
+
+/// Looks up the on-disk keymap file for `layout`, so a selection made
+/// earlier in the session (or restored from a saved config) can be checked
+/// right before the configure step runs `loadkeys` with it — package
+/// removals or a stale config shouldn't surface as a raw `loadkeys` failure
+/// with no indication of which layout was at fault.
+pub fn resolve_keymap_path(layout: &str) -> Result<Option<PathBuf>> {
+    let pattern = format!("{layout}.map.gz");
+    let output = Command::new("find").args([KEYMAP_DIR, "-iname", &pattern, "-print", "-quit"]).read()?;
+    Ok(first_nonempty_line(&output).map(PathBuf::from))
+}
+
+fn first_nonempty_line(output: &str) -> Option<&str> {
+    output.lines().find(|line| !line.trim().is_empty())
+}
+
+pub struct Keyboard {
+    menu: Menu,
+    favorites: Favorites,
+}
+
+impl Keyboard {
+    pub fn new(config: &Config) -> Self {
+        let favorites = Favorites::load();
+        let items = favorites.pin(MENU_NAME, layouts());
+        let mut menu = Menu::new(items.clone(), MenuArgs::default().title("Keyboard layout".into()));
+        if let Some(current) = &config.keyboard_layout {
+            if let Some(index) = items.iter().position(|item| item == current) {
+                menu.select(Some(index));
+            }
+        }
+        Self { menu, favorites }
+    }
+
+    /// Stars/un-stars the selected layout and re-pins the list so the change
+    /// is reflected immediately.
+    fn toggle_favorite(&mut self) {
+        let Some(current) = self.menu.current_item().cloned() else {
+            return;
+        };
+        self.favorites.toggle(MENU_NAME, &current);
+        let _ = self.favorites.save();
+        let items = self.favorites.pin(MENU_NAME, layouts());
+        self.menu.update_items(items.clone());
+        self.menu.select(items.iter().position(|item| *item == current));
+    }
+}
+
+impl View for Keyboard {
+    fn render(&mut self, frame: &mut Frame, _config: &Config) {
+        let (body, hint) = split_body_and_hint(frame.size());
+        self.menu.render(frame, body);
+        render_keybinding_hint(frame, hint, FAVORITES_TIP);
+    }
+
+    fn on_event(&mut self, event: Event, config: &mut Config) -> Result<Option<Msg>> {
+        if is_clear_key(&event) {
+            config.keyboard_layout = None;
+            self.menu.select(None);
+            return Ok(None);
+        }
+
+        if let Event::Mouse(mouse) = &event {
+            self.menu.handle_mouse(mouse);
+            return Ok(None);
+        }
+
+        if let Event::Key(key) = event {
+            if key.kind != KeyEventKind::Press {
+                return Ok(None);
+            }
+            match key.code {
+                KeyCode::Down | KeyCode::Char('j') => self.menu.selectable.select_next_item(),
+                KeyCode::Up | KeyCode::Char('k') => self.menu.selectable.select_prev_item(),
+                KeyCode::Char('*') => self.toggle_favorite(),
+                KeyCode::Enter => {
+                    if let Some(layout) = self.menu.current_item() {
+                        config.keyboard_layout = Some(layout.clone());
+                    }
+                    return Ok(Some(Msg::Pop));
+                }
+                KeyCode::Esc => return Ok(Some(Msg::Pop)),
+                // Type-ahead jump, e.g. pressing 'f' jumps to "fr" — a fast
+                // coarse move through the list. Any command letter
+                // above (j, k, *) is matched first, so it never reaches here.
+                KeyCode::Char(c) => {
+                    self.menu.jump_to_prefix(c);
+                }
+                _ => {}
+            }
+        }
+        Ok(None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn first_nonempty_line_skips_leading_blank_output() {
+        assert_eq!(first_nonempty_line("\n/usr/share/kbd/keymaps/i386/qwerty/us.map.gz\n"), Some("/usr/share/kbd/keymaps/i386/qwerty/us.map.gz"));
+    }
+
+    #[test]
+    fn first_nonempty_line_is_none_for_empty_find_output() {
+        assert_eq!(first_nonempty_line(""), None);
+        assert_eq!(first_nonempty_line("\n\n"), None);
+    }
+}
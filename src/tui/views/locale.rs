@@ -0,0 +1,181 @@
+use anyhow::Result;
+use crossterm::event::{Event, KeyCode, KeyEventKind};
+use ratatui::Frame;
+
+use crate::config::Config;
+use crate::tui::views::{render_keybinding_hint, split_body_and_hint, MasterDetail, Msg, Pane, View};
+
+const TIP: &str = "↑/↓ j/k move · Tab/h/l switch pane · Enter select · Esc back";
+
+/// Region -> representative locale list. Hard-coded for now; a real
+/// implementation would enumerate `/usr/share/i18n/locales`.
+fn regions() -> Vec<(&'static str, &'static [&'static str])> {
+    vec![
+        ("English", &["en_US.UTF-8", "en_GB.UTF-8"]),
+        ("German", &["de_DE.UTF-8", "de_AT.UTF-8"]),
+        ("French", &["fr_FR.UTF-8", "fr_CA.UTF-8"]),
+        ("Japanese", &["ja_JP.UTF-8"]),
+    ]
+}
+
+/// A stock `/etc/locale.gen`-style listing (`locale encoding` per line, `#`
+/// comments and blank lines ignored) used to look up which encodings a
+/// locale supports before committing it.
+const LOCALE_GEN: &str = "\
+en_US.UTF-8 UTF-8
+en_GB.UTF-8 UTF-8
+de_DE.UTF-8 UTF-8
+de_AT.UTF-8 UTF-8
+fr_FR.UTF-8 UTF-8
+fr_CA.UTF-8 UTF-8
+ja_JP.UTF-8 UTF-8
+";
+
+/// Extracts the encoding tokens listed for `locale` out of a
+/// `locale.gen`-style listing.
+fn encodings_for(locale: &str, locale_gen: &str) -> Vec<String> {
+    locale_gen
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter_map(|line| {
+            let mut parts = line.split_whitespace();
+            let name = parts.next()?;
+            let encoding = parts.next()?;
+            (name == locale).then(|| encoding.to_string())
+        })
+        .collect()
+}
+
+/// Resolves the encoding to commit for a chosen locale, short-circuiting the
+/// two cases where making the user step through a trivial `Encoding` menu
+/// would just be busywork: no candidates found (default to `UTF-8`, which
+/// covers every locale this app currently offers) or exactly one (use it
+/// outright). More than one candidate returns `None`; `Locale::on_event` has
+/// no `Encoding` prompt and falls back to `UTF-8` in that case.
+fn resolve_encoding(candidates: &[String]) -> Option<String> {
+    match candidates {
+        [] => Some("UTF-8".to_string()),
+        [only] => Some(only.clone()),
+        _ => None,
+    }
+}
+
+/// Region/locale picker: the left pane lists regions, the right pane lists
+/// that region's locales, kept in sync as the left selection moves.
+pub struct Locale {
+    master_detail: MasterDetail,
+}
+
+impl Locale {
+    pub fn new(config: &Config) -> Self {
+        let regions = regions();
+        let region_names: Vec<String> = regions.iter().map(|(name, _)| name.to_string()).collect();
+        let mut master_detail = MasterDetail::new("Region", region_names, "Locale", Vec::new());
+        master_detail.left.select(Some(0));
+        Self::sync_locales(&mut master_detail, &regions);
+
+        if let Some(current) = &config.locale {
+            for (region_index, (_, locales)) in regions.iter().enumerate() {
+                if let Some(locale_index) = locales.iter().position(|l| *l == current) {
+                    master_detail.left.select(Some(region_index));
+                    Self::sync_locales(&mut master_detail, &regions);
+                    master_detail.right.select(Some(locale_index));
+                    break;
+                }
+            }
+        }
+
+        Self { master_detail }
+    }
+
+    fn sync_locales(master_detail: &mut MasterDetail, regions: &[(&'static str, &'static [&'static str])]) {
+        let Some(index) = master_detail.left.current_index() else {
+            return;
+        };
+        let locales = regions[index].1.iter().map(|s| s.to_string()).collect();
+        master_detail.right.update_items(locales);
+        master_detail.right.select(Some(0));
+    }
+}
+
+impl View for Locale {
+    fn render(&mut self, frame: &mut Frame, _config: &Config) {
+        let (body, hint) = split_body_and_hint(frame.size());
+        self.master_detail.render(frame, body);
+        render_keybinding_hint(frame, hint, TIP);
+    }
+
+    fn on_event(&mut self, event: Event, config: &mut Config) -> Result<Option<Msg>> {
+        if let Event::Mouse(mouse) = &event {
+            if self.master_detail.handle_mouse(mouse) == Some(Pane::Left) {
+                Self::sync_locales(&mut self.master_detail, &regions());
+            }
+            return Ok(None);
+        }
+
+        if let Event::Key(key) = event {
+            if key.kind != KeyEventKind::Press {
+                return Ok(None);
+            }
+
+            let was_left = self.master_detail.focus == Pane::Left;
+            if self.master_detail.handle_key(key.code) {
+                if was_left {
+                    Self::sync_locales(&mut self.master_detail, &regions());
+                }
+                return Ok(None);
+            }
+
+            match key.code {
+                KeyCode::Enter => {
+                    if let Some(locale) = self.master_detail.right.current_item() {
+                        // Every locale currently offered lists exactly one
+                        // encoding in LOCALE_GEN, so this always resolves
+                        // without a separate `Encoding` step. A locale with
+                        // real alternatives (e.g. `en_DK`, which ships both
+                        // UTF-8 and ISO-8859-1) lands in the `None` arm, which
+                        // takes UTF-8 without asking.
+                        let encoding = resolve_encoding(&encodings_for(locale, LOCALE_GEN))
+                            .unwrap_or_else(|| "UTF-8".to_string());
+                        crate::logger::log_event(
+                            "locale-selected",
+                            &[("locale", locale.as_str()), ("encoding", &encoding)],
+                        );
+                        config.locale = Some(locale.clone());
+                    }
+                    return Ok(Some(Msg::Pop));
+                }
+                KeyCode::Esc => return Ok(Some(Msg::Pop)),
+                _ => {}
+            }
+        }
+        Ok(None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn a_single_matching_encoding_is_auto_selected() {
+        let fixture = "en_US.UTF-8 UTF-8\n";
+        let candidates = encodings_for("en_US.UTF-8", fixture);
+        assert_eq!(resolve_encoding(&candidates), Some("UTF-8".to_string()));
+    }
+
+    #[test]
+    fn no_matching_encoding_defaults_to_utf8() {
+        let candidates = encodings_for("xx_XX.UTF-8", "en_US.UTF-8 UTF-8\n");
+        assert!(candidates.is_empty());
+        assert_eq!(resolve_encoding(&candidates), Some("UTF-8".to_string()));
+    }
+
+    #[test]
+    fn more_than_one_encoding_needs_a_prompt() {
+        let fixture = "en_DK UTF-8\nen_DK ISO-8859-1\n";
+        let candidates = encodings_for("en_DK", fixture);
+        assert_eq!(resolve_encoding(&candidates), None);
+    }
+}
@@ -0,0 +1,165 @@
+use std::path::PathBuf;
+
+use anyhow::Result;
+use crossterm::event::{Event, KeyCode, KeyEventKind};
+use ratatui::Frame;
+
+use crate::config::Config;
+use crate::tui::data::partition::lsblk;
+use crate::tui::views::console_font::ConsoleFont;
+use crate::tui::views::keyboard::Keyboard;
+use crate::tui::views::locale::Locale;
+use crate::tui::views::partition::PartitionView;
+use crate::tui::views::summary::Summary;
+use crate::tui::views::timezone::Timezone;
+use crate::tui::views::{render_keybinding_hint, split_body_and_hint, Msg, Operation, View, NAVIGATION_TIP};
+use crate::tui::widgets::confirm::{Confirm, ConfirmArgs};
+use crate::tui::widgets::menu::{Menu, MenuArgs};
+
+const ENTRIES: &[&str] =
+    &["Keyboard layout", "Console font", "Timezone", "Locale", "Partition disks", "Preview generated configs"];
+/// Shown instead of `ENTRIES` when `chroot::is_chroot()` — partitioning and
+/// basestrap don't make sense against an already-installed system being
+/// reconfigured from inside its own chroot, so only the configuration steps
+/// remain.
+const CHROOT_ENTRIES: &[&str] = &["Keyboard layout", "Console font", "Timezone", "Locale", "Preview generated configs"];
+
+/// The main menu. Each entry shows a checkmark once the corresponding
+/// `Config` field is set, so the user can see progress and, via the
+/// deselect key handled in each sub-view, undo it.
+pub struct Main {
+    menu: Menu,
+    /// Whether we're running inside an existing chroot, in which case
+    /// `entries`/`on_event` fall back to `CHROOT_ENTRIES` and its shorter
+    /// index mapping.
+    chroot: bool,
+    /// Device to preselect in `PartitionView`, from the `--device` CLI flag.
+    preselect_device: Option<PathBuf>,
+    /// Whether the quit confirmation popup is open, asking the user to
+    /// confirm before `q` actually closes the installer.
+    show_quit_confirm: bool,
+    quit_confirm: Confirm,
+}
+
+impl Main {
+    pub fn new(config: &Config) -> Self {
+        Self::new_with_device(config, None)
+    }
+
+    pub fn new_with_device(config: &Config, preselect_device: Option<PathBuf>) -> Self {
+        let chroot = crate::chroot::is_chroot();
+        let title = if chroot { "Artixide (chroot detected)" } else { "Artixide" };
+        Self {
+            menu: Menu::new(Self::labels(config, chroot), MenuArgs::default().title(title.into())),
+            chroot,
+            preselect_device,
+            show_quit_confirm: false,
+            quit_confirm: Confirm::new(ConfirmArgs::default().message("Quit? y/n".into())),
+        }
+    }
+
+    /// Enumerates real devices via `lsblk` and builds the partition view,
+    /// preselecting `preselect_device` if one was given on the command line.
+    /// Enumeration failures (no `lsblk`, permission issues) fall back to an
+    /// empty device list rather than failing the whole menu action.
+    fn open_partition_view(&self) -> Box<dyn View> {
+        let devices = lsblk::get_devices().unwrap_or_else(|err| {
+            log::warn!("failed to enumerate devices: {err:#}");
+            Vec::new()
+        });
+        let mut view = PartitionView::new(devices);
+        if let Some(path) = &self.preselect_device {
+            if !view.select_device_by_path(path) {
+                log::warn!("--device {} did not match any detected device", path.display());
+            }
+        }
+        Box::new(view)
+    }
+
+    fn entries(chroot: bool) -> &'static [&'static str] {
+        if chroot {
+            CHROOT_ENTRIES
+        } else {
+            ENTRIES
+        }
+    }
+
+    fn labels(config: &Config, chroot: bool) -> Vec<String> {
+        let mut done = vec![
+            config.keyboard_layout.is_some(),
+            config.console_font.is_some(),
+            config.timezone.is_some(),
+            config.locale.is_some(),
+            false,
+        ];
+        if !chroot {
+            done.push(false);
+        }
+        Self::entries(chroot)
+            .iter()
+            .zip(done)
+            .map(|(label, done)| {
+                if done {
+                    format!("[x] {label}")
+                } else {
+                    format!("[ ] {label}")
+                }
+            })
+            .collect()
+    }
+}
+
+impl View for Main {
+    fn render(&mut self, frame: &mut Frame, config: &Config) {
+        let (body, hint) = split_body_and_hint(frame.size());
+        self.menu.update_items(Self::labels(config, self.chroot));
+        self.menu.render(frame, body);
+        render_keybinding_hint(frame, hint, NAVIGATION_TIP);
+
+        if self.show_quit_confirm {
+            self.quit_confirm.render(frame, frame.size());
+        }
+    }
+
+    fn on_event(&mut self, event: Event, config: &mut Config) -> Result<Option<Msg>> {
+        if self.show_quit_confirm {
+            match self.quit_confirm.on_event(&event) {
+                Some(true) => return Ok(Some(Msg::Close(Operation::Quit))),
+                Some(false) => self.show_quit_confirm = false,
+                None => {}
+            }
+            return Ok(None);
+        }
+
+        if let Event::Mouse(mouse) = &event {
+            self.menu.handle_mouse(mouse);
+            return Ok(None);
+        }
+
+        if let Event::Key(key) = event {
+            if key.kind != KeyEventKind::Press {
+                return Ok(None);
+            }
+            match key.code {
+                KeyCode::Down | KeyCode::Char('j') => self.menu.selectable.select_next_item(),
+                KeyCode::Up | KeyCode::Char('k') => self.menu.selectable.select_prev_item(),
+                KeyCode::Enter => {
+                    return Ok(match (self.chroot, self.menu.current_index()) {
+                        (_, Some(0)) => Some(Msg::Push(Box::new(Keyboard::new(config)))),
+                        (_, Some(1)) => Some(Msg::Push(Box::new(ConsoleFont::new(config)))),
+                        (_, Some(2)) => Some(Msg::Push(Box::new(Timezone::new(config)))),
+                        (_, Some(3)) => Some(Msg::Push(Box::new(Locale::new(config)))),
+                        (false, Some(4)) => Some(Msg::Push(self.open_partition_view())),
+                        (false, Some(5)) => Some(Msg::Push(Box::new(Summary::new(config)))),
+                        (true, Some(4)) => Some(Msg::Push(Box::new(Summary::new(config)))),
+                        _ => None,
+                    });
+                }
+                KeyCode::Char('q') => self.show_quit_confirm = true,
+                KeyCode::Esc => return Ok(Some(Msg::Close(Operation::Quit))),
+                _ => {}
+            }
+        }
+        Ok(None)
+    }
+}
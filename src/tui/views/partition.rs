@@ -0,0 +1,1745 @@
+pub mod editor;
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::process::Command;
+
+use anyhow::Result;
+use crossterm::event::{Event, KeyCode, KeyEventKind, KeyModifiers};
+use ratatui::{
+    layout::Constraint,
+    style::{Color, Modifier, Style},
+    text::Line,
+    widgets::{Block, Borders, Clear, Paragraph, Row, Table},
+    Frame,
+};
+
+use crate::command::CommandExt;
+use crate::config::Config;
+use crate::tui::data::partition::{
+    format_size, ByteSize, CompatDevice, Device, FileSystem, GptAttributes, MemPartition, MemTableEntry, TableType,
+};
+use crate::tui::views::all_partitions::AllPartitions;
+use crate::tui::views::partition::editor::DiskEditor;
+use crate::tui::views::{
+    centered_rect, render_keybinding_hint, split_body_and_hint, Msg, View,
+};
+use crate::tui::widgets::input::{EmptyEnter, Input, InputArgs, InputCommand};
+use crate::tui::widgets::menu::searchable::SearchableMenu;
+use crate::tui::widgets::menu::{Menu, MenuArgs};
+use crate::tui::widgets::toggle_list::{ToggleList, ToggleListArgs};
+
+const PARTITION_TIP: &str =
+    "i details · d switch device · a alias · f jump to fit · L largest free · n new table · y duplicate size · G gpt attrs · s set filesystem · m set mountpoint · l set label · W wipe disk · A apply · Ctrl+z discard changes · R read-only · v all partitions · }/{ jump free space · Esc back";
+const READ_ONLY_TIP: &str =
+    "i details · d switch device · L largest free · v all partitions · }/{ jump free space · R exit read-only · Esc back";
+
+/// Note appended to `device_summary` for a disk whose sector size isn't the
+/// common 512 bytes (e.g. a 4Kn disk), so a sector count shown elsewhere
+/// (the create-flow legend, `details_text`) isn't silently misread against
+/// the wrong sector size. Suppressed for 512-byte-sector disks since that's
+/// the overwhelmingly common case and calling it out everywhere would just
+/// be noise.
+fn sector_size_note(sector_size: u64) -> String {
+    if sector_size == 512 {
+        String::new()
+    } else {
+        format!(", {sector_size}-byte sectors")
+    }
+}
+
+/// One-line "model — path (size)" summary shared by the device-details popup
+/// and the device-switch picker, so the two stay in sync. `alias` is a
+/// session-only display name set through the `a` keybinding; it's cosmetic
+/// only and never affects which device operations target.
+fn device_summary(device: &Device, alias: Option<&str>) -> String {
+    let disk = device.disk();
+    let sector_note = sector_size_note(disk.sector_size);
+    match alias {
+        Some(alias) => {
+            format!("\"{alias}\" {} — {} ({}{sector_note})", disk.model, disk.path.display(), format_size(disk.size))
+        }
+        None => format!("{} — {} ({}{sector_note})", disk.model, disk.path.display(), format_size(disk.size)),
+    }
+}
+
+/// The partition-planning screen: a device list/table on one side and a
+/// `DiskEditor` for create/delete actions on the other.
+pub struct PartitionView {
+    pub devices: Vec<Device>,
+    /// Snapshot of `devices` as they were enumerated on entry, before any
+    /// in-session edits. Backs `discard_changes` (`Ctrl+Z`), the per-device
+    /// counterpart to undo — indices line up 1:1 with `devices`.
+    original_devices: Vec<Device>,
+    pub current_device: usize,
+    pub editor: DiskEditor,
+    /// Index into the current device's `mem_table`.
+    pub table_selected: Option<usize>,
+    show_details: bool,
+    /// Popup listing every device by `device_summary`, for jumping straight
+    /// to a disk instead of cycling next/prev one at a time.
+    device_menu: Menu,
+    show_device_menu: bool,
+    /// Size prompt for "jump to the first free region that fits this".
+    jump_input: Input,
+    show_jump_prompt: bool,
+    /// Set when a jump search comes up empty, shown until the next attempt.
+    jump_error: Option<String>,
+    /// Session-only display aliases, keyed by device path, set through `a`.
+    aliases: HashMap<PathBuf, String>,
+    alias_input: Input,
+    show_alias_prompt: bool,
+    /// Strong confirmation for `wipe_disk`: the user must type the device's
+    /// exact path back, not just press y/n, given how destructive it is.
+    wipe_confirm_input: Input,
+    show_wipe_confirm: bool,
+    wipe_error: Option<String>,
+    /// When set, hides/disables every mutating action (create, delete, wipe,
+    /// duplicate-size) so the device can be inspected without any chance of
+    /// touching it. Toggled with `R`.
+    read_only: bool,
+    /// The last-chance confirmation before writing the table: a sector-level
+    /// listing of every planned partition, shown by `A`.
+    show_apply_confirm: bool,
+    /// Set when `apply::apply_device` fails partway through, shown until the
+    /// next keypress like `wipe_error`. A backup was already taken before
+    /// the write, per `apply::backup_partition_table`'s doc comment.
+    apply_error: Option<String>,
+    /// Confirmation shown on `Esc` when the current device has unsaved
+    /// changes (`CompatDevice::is_dirty`), so a stray `Esc` can't silently
+    /// discard planned partitions.
+    show_leave_confirm: bool,
+    /// Transient message shown after a `L` "jump to largest free region",
+    /// reporting its size. Dismissed by the next keypress, like `jump_error`.
+    status_note: Option<String>,
+    /// Confirmation for `discard_changes`, shown only when the current
+    /// device actually has pending modifications to lose.
+    show_discard_confirm: bool,
+    /// GPT attribute-bit toggles for the selected partition, opened by `G`.
+    /// Only meaningful on a `TableType::Gpt` device — see
+    /// `can_edit_gpt_attributes`.
+    gpt_attributes_editor: ToggleList,
+    show_gpt_attributes: bool,
+    /// Filesystem picker for the selected partition, opened by `s`. Unlike
+    /// `editor.filesystem_picker` (which only ever feeds the create flow),
+    /// this is seeded fresh from the selected partition's current
+    /// filesystem each time it's opened.
+    filesystem_editor: SearchableMenu,
+    show_filesystem_editor: bool,
+    /// Mirrors `show_filesystem_editor`/`filesystem_editor`, but for
+    /// `editor.mountpoint_input`, opened by `m`.
+    show_mountpoint_editor: bool,
+    /// Set when `DiskEditor::handle_set_mountpoint` rejects the typed path,
+    /// shown until the next keypress like `wipe_error`.
+    mountpoint_error: Option<String>,
+    /// Mirrors `show_filesystem_editor`/`filesystem_editor`, but for
+    /// `editor.label_input`, opened by `l`.
+    show_label_editor: bool,
+    /// Set when `DiskEditor::handle_set_label` rejects the typed label,
+    /// shown until the next keypress like `wipe_error`.
+    label_error: Option<String>,
+}
+
+impl PartitionView {
+    pub fn new(devices: Vec<Device>) -> Self {
+        let items = devices.iter().map(|d| device_summary(d, None)).collect();
+        let mut device_menu = Menu::new(items, MenuArgs::default().title("Switch device".into()));
+        device_menu.select(if devices.is_empty() { None } else { Some(0) });
+        Self {
+            original_devices: devices.clone(),
+            devices,
+            current_device: 0,
+            editor: DiskEditor::default(),
+            table_selected: None,
+            show_details: false,
+            device_menu,
+            show_device_menu: false,
+            jump_input: Input::new(InputArgs::default().title("Jump to free space fitting".into())),
+            show_jump_prompt: false,
+            jump_error: None,
+            aliases: HashMap::new(),
+            alias_input: Input::new(
+                InputArgs::default().title("Alias for this device".into()).on_empty_enter(EmptyEnter::SubmitEmpty),
+            ),
+            show_alias_prompt: false,
+            wipe_confirm_input: Input::new(InputArgs::default().title("Type the device path to confirm".into())),
+            show_wipe_confirm: false,
+            wipe_error: None,
+            read_only: false,
+            show_apply_confirm: false,
+            apply_error: None,
+            show_leave_confirm: false,
+            status_note: None,
+            show_discard_confirm: false,
+            gpt_attributes_editor: ToggleList::default(),
+            show_gpt_attributes: false,
+            filesystem_editor: SearchableMenu::new(Vec::new(), MenuArgs::default().title("Filesystem".into())),
+            show_filesystem_editor: false,
+            show_mountpoint_editor: false,
+            mountpoint_error: None,
+            show_label_editor: false,
+            label_error: None,
+        }
+    }
+
+    /// Reverts the current device to its `original_devices` snapshot,
+    /// discarding every in-session edit (planned partitions, deletions,
+    /// shrinks) at once. The per-device counterpart to undo: there's no
+    /// step-by-step history, just "back to how it was when this view opened".
+    fn discard_changes(&mut self) {
+        if let Some(original) = self.original_devices.get(self.current_device) {
+            self.devices[self.current_device] = original.clone();
+        }
+        self.table_selected = None;
+        self.editor = DiskEditor::default();
+    }
+
+    /// Color scheme for the browsable partition table, so a row's state is
+    /// obvious without reading a status column:
+    ///
+    /// - green: free space (`MemTableEntry::Free`).
+    /// - cyan: a planned partition (`!MemPartition::is_real()`) — created
+    ///   this session but not yet written by `apply`.
+    /// - red: a real, already-on-disk partition whose filesystem couldn't be
+    ///   identified (`FileSystem::Unknown`) — worth a second look before
+    ///   formatting over it.
+    /// - the terminal's default style: a real partition with a known
+    ///   filesystem — nothing to flag.
+    fn row_style(entry: &MemTableEntry) -> Style {
+        match entry {
+            MemTableEntry::Free(_) => Style::default().fg(Color::Green),
+            MemTableEntry::Partition(partition) if !partition.is_real() => Style::default().fg(Color::Cyan),
+            MemTableEntry::Partition(partition) if partition.filesystem == FileSystem::Unknown => {
+                Style::default().fg(Color::Red)
+            }
+            MemTableEntry::Partition(_) => Style::default(),
+        }
+    }
+
+    /// Rows for the main browsable partition table, one per `mem_table`
+    /// entry (free space included, unlike `apply_preview_rows`), styled per
+    /// `row_style` and reverse-video highlighted when selected.
+    fn partition_table_rows(&self) -> Vec<Row> {
+        let Some(table) = self.mem_table() else { return Vec::new() };
+        let sector_size = self.devices[self.current_device].disk().sector_size;
+        table
+            .iter()
+            .enumerate()
+            .map(|(index, entry)| {
+                let (number, start, sectors, filesystem) = match entry {
+                    MemTableEntry::Partition(partition) => (
+                        partition.number.to_string(),
+                        partition.start,
+                        partition.sectors,
+                        partition.filesystem.as_ref().to_string(),
+                    ),
+                    MemTableEntry::Free(space) => ("-".to_string(), space.start, space.sectors, "free".to_string()),
+                };
+                let end = start + sectors - 1;
+                let cells = vec![number, start.to_string(), end.to_string(), format_size(sectors * sector_size), filesystem];
+                let mut style = Self::row_style(entry);
+                if self.table_selected == Some(index) {
+                    style = style.add_modifier(Modifier::REVERSED);
+                }
+                Row::new(cells).style(style)
+            })
+            .collect()
+    }
+
+    /// Builds the sector-level confirmation table shown by `A`: one row per
+    /// planned partition on the current device, with its exact start/end
+    /// sectors, size, and filesystem — the ground truth of what `apply` is
+    /// about to write. This is deliberately more precise than the device
+    /// summary line, since it's the last chance to catch a mistake before a
+    /// destructive write.
+    fn apply_preview_rows(&self) -> Vec<Row> {
+        let Some(table) = self.mem_table() else { return Vec::new() };
+        let sector_size = self.devices[self.current_device].disk().sector_size;
+        table
+            .iter()
+            .filter_map(|entry| match entry {
+                MemTableEntry::Partition(partition) => Some(partition),
+                MemTableEntry::Free(_) => None,
+            })
+            .map(|partition| {
+                let end = partition.start + partition.sectors - 1;
+                Row::new(vec![
+                    partition.number.to_string(),
+                    partition.start.to_string(),
+                    end.to_string(),
+                    format_size(partition.sectors * sector_size),
+                    partition.filesystem.as_ref().to_string(),
+                    if partition.is_real() { "existing".to_string() } else { "new".to_string() },
+                ])
+            })
+            .collect()
+    }
+
+    /// Selects the device at `path`, if present, resetting the table
+    /// selection and editor since they refer to whatever was selected
+    /// before. Used to preselect a device passed on the command line
+    /// (`--device`) so the user lands directly on it instead of the first
+    /// device `lsblk` happened to list first. Returns whether a match was
+    /// found.
+    pub fn select_device_by_path(&mut self, path: &std::path::Path) -> bool {
+        let Some(index) = self.devices.iter().position(|d| d.disk().path == path) else {
+            return false;
+        };
+        self.current_device = index;
+        self.table_selected = None;
+        self.editor = DiskEditor::default();
+        true
+    }
+
+    /// Replaces the device list — e.g. after a rescan turns up a different
+    /// set of disks — and clamps `current_device` into bounds. `devices` is
+    /// `pub` for callers that hand a fresh list to an already-open view;
+    /// without this, a shrunk (or emptied) list left `current_device`
+    /// pointing past the end, and the next `self.devices[self.current_device]`
+    /// (`discard_changes`, `partition_table_rows`, ...) would panic.
+    pub fn set_devices(&mut self, devices: Vec<Device>) {
+        self.devices = devices.clone();
+        self.original_devices = devices;
+        self.current_device = self.current_device.min(self.devices.len().saturating_sub(1));
+        self.table_selected = None;
+        self.editor = DiskEditor::default();
+        self.show_device_menu = false;
+    }
+
+    fn alias_for(&self, device: &Device) -> Option<&str> {
+        self.aliases.get(&device.disk().path).map(String::as_str)
+    }
+
+    fn mem_table(&self) -> Option<&[MemTableEntry]> {
+        match self.devices.get(self.current_device)? {
+            Device::Compatible(dev) => Some(&dev.mem_table),
+            Device::Incompatible(_) => None,
+        }
+    }
+
+    /// Whether the current device has planned changes not yet written to
+    /// disk, per `CompatDevice::is_dirty`. Backs the `*` shown in the title
+    /// and the "leave with unsaved changes?" prompt on `Esc`.
+    fn current_device_is_dirty(&self) -> bool {
+        matches!(self.devices.get(self.current_device), Some(Device::Compatible(dev)) if dev.is_dirty())
+    }
+
+    /// Handles keys that operate on the partition table itself, as opposed to
+    /// the device list or the editor panel: `}`/`{` jump the selection to the
+    /// next/previous `MemTableEntry::Free` region, wrapping around at either
+    /// end. Skips straight past partitions rather than stepping through them.
+    pub fn handle_table(&mut self, key: KeyCode) {
+        let Some(table) = self.mem_table() else { return };
+        if table.is_empty() {
+            return;
+        }
+        let is_free = |i: usize| matches!(table[i], MemTableEntry::Free(_));
+        let len = table.len();
+        let current = self.table_selected.unwrap_or(0);
+
+        let next = match key {
+            KeyCode::Char('}') => (1..=len)
+                .map(|offset| (current + offset) % len)
+                .find(|&i| is_free(i)),
+            KeyCode::Char('{') => (1..=len)
+                .map(|offset| (current + len - offset) % len)
+                .find(|&i| is_free(i)),
+            _ => None,
+        };
+        if let Some(next) = next {
+            self.table_selected = Some(next);
+        }
+    }
+
+    /// Jumps the table selection to the `MemTableEntry::Free` region with the
+    /// most sectors on the current device — usually where a user planning a
+    /// new partition on a fragmented disk wants to start. Reports the size
+    /// via `status_note`; does nothing (and leaves no note) if the device has
+    /// no free space at all.
+    fn jump_to_largest_free_region(&mut self) {
+        let sector_size = self.devices.get(self.current_device).map(|d| d.disk().sector_size).unwrap_or(512);
+        let Some(table) = self.mem_table() else { return };
+        let largest = table
+            .iter()
+            .enumerate()
+            .filter_map(|(index, entry)| match entry {
+                MemTableEntry::Free(space) => Some((index, space.sectors)),
+                MemTableEntry::Partition(_) => None,
+            })
+            .max_by_key(|&(_, sectors)| sectors);
+
+        if let Some((index, sectors)) = largest {
+            self.table_selected = Some(index);
+            self.status_note = Some(format!("Jumped to the largest free region: {}", format_size(sectors * sector_size)));
+        }
+    }
+
+    /// Formats the Secure Boot line appended to `details_text`, given the
+    /// already-read `firmware::secure_boot_enabled()` result — kept as a pure
+    /// function of that `Option<bool>` so it's testable without a real
+    /// `/sys/firmware/efi` to read.
+    fn secure_boot_text(status: Option<bool>) -> String {
+        let state = match status {
+            Some(true) => "enabled",
+            Some(false) => "disabled",
+            None => "unknown (not UEFI, or undetectable)",
+        };
+        format!("\nSecure Boot: {state}")
+    }
+
+    /// Formats the model/path/size/sector-size/table-type/partition-count
+    /// summary shown in the `i` details popup, plus an alignment-loss note
+    /// for the selected row if it's a free region (see `alignment_loss_text`).
+    fn details_text(&self) -> String {
+        let Some(device) = self.devices.get(self.current_device) else {
+            return "No device selected.".to_string();
+        };
+        let disk = device.disk();
+        let partitions = match device {
+            Device::Compatible(dev) => dev
+                .mem_table
+                .iter()
+                .filter(|entry| matches!(entry, crate::tui::data::partition::MemTableEntry::Partition(_)))
+                .count(),
+            Device::Incompatible(_) => 0,
+        };
+        let table_type = match disk.table_type {
+            TableType::Gpt => "GPT",
+            TableType::Mbr => "MBR",
+            TableType::None => "none",
+        };
+        let usage = match device {
+            Device::Compatible(dev) => {
+                let (used, free) = dev.used_and_free_bytes();
+                format!("\nUsed: {} · Free: {}", format_size(used), format_size(free))
+            }
+            Device::Incompatible(_) => String::new(),
+        };
+        format!(
+            "{}\nSector size: {} bytes\nTable: {table_type}\nPartitions: {partitions}{usage}{}{}{}",
+            device_summary(device, self.alias_for(device)),
+            disk.sector_size,
+            Self::secure_boot_text(crate::firmware::secure_boot_enabled()),
+            self.alignment_loss_text(),
+            self.gpt_attributes_text(),
+        )
+    }
+
+    /// If the selected table row is a partition with at least one GPT
+    /// attribute flag set, reports which — appended to `details_text` the
+    /// same way `alignment_loss_text` reports free-region alignment loss.
+    /// Empty otherwise, so the common "no flags set" case adds nothing.
+    fn gpt_attributes_text(&self) -> String {
+        let Some(partition) = self.selected_partition() else { return String::new() };
+        let label = partition.gpt_attributes.label();
+        if label.is_empty() {
+            String::new()
+        } else {
+            format!("\nGPT attributes: {label}")
+        }
+    }
+
+    /// If the selected table row is a free region, and creating a partition
+    /// filling it would lose sectors to the `1MiB` alignment boundary (see
+    /// `editor::align_sectors`), explains where those "missing" sectors go.
+    /// Empty otherwise, so the common case adds nothing to the popup.
+    fn alignment_loss_text(&self) -> String {
+        let sector_size = self.devices.get(self.current_device).map(|d| d.disk().sector_size).unwrap_or(512);
+        let Some(table) = self.mem_table() else { return String::new() };
+        let Some(MemTableEntry::Free(space)) = self.table_selected.and_then(|i| table.get(i)) else {
+            return String::new();
+        };
+        let align = editor::align_sectors(sector_size);
+        let aligned_end = ((space.start + space.sectors) / align) * align;
+        let lost = (space.start + space.sectors).saturating_sub(aligned_end.max(space.start));
+        if lost == 0 {
+            return String::new();
+        }
+        format!(
+            "\n{lost} sector(s) ({}) at the end of this region are reserved for {}-sector alignment.",
+            format_size(lost * sector_size),
+            align,
+        )
+    }
+
+    /// Opens the device-switch popup, syncing its items and selection with
+    /// the current device list first in case devices were rescanned.
+    fn open_device_menu(&mut self) {
+        let items = self.devices.iter().map(|d| device_summary(d, self.alias_for(d))).collect();
+        self.device_menu.update_items(items);
+        self.device_menu.select(Some(self.current_device));
+        self.show_device_menu = true;
+    }
+
+    /// Sets (or, if `text` is empty, clears) the display alias for the
+    /// current device. Purely cosmetic — it's never consulted when choosing
+    /// which device an operation targets.
+    fn set_alias(&mut self, text: &str) {
+        let Some(device) = self.devices.get(self.current_device) else { return };
+        let path = device.disk().path.clone();
+        if text.trim().is_empty() {
+            self.aliases.remove(&path);
+        } else {
+            self.aliases.insert(path, text.trim().to_string());
+        }
+    }
+
+    /// Switches to the device picked in `device_menu`, resetting the table
+    /// selection and editor since they refer to the previous device's state.
+    fn switch_device(&mut self) {
+        if let Some(index) = self.device_menu.current_index() {
+            self.current_device = index;
+            self.table_selected = None;
+            self.editor = DiskEditor::default();
+            if let Some(device) = self.devices.get(index) {
+                let path = device.disk().path.to_string_lossy().into_owned();
+                crate::logger::log_event("device-selected", &[("path", &path)]);
+            }
+        }
+        self.show_device_menu = false;
+    }
+
+    /// Parses `text` as a size and selects the first `MemTableEntry::Free`
+    /// region big enough to hold it, leaving `jump_error` set if none fits.
+    fn jump_to_fitting_free_space(&mut self, text: &str) {
+        self.jump_error = None;
+        let Some(device) = self.devices.get(self.current_device) else {
+            return;
+        };
+        let sector_size = device.disk().sector_size;
+
+        let needed_sectors = match text.trim().parse::<ByteSize>() {
+            Ok(size) => size.to_sectors(sector_size),
+            Err(err) => {
+                self.jump_error = Some(err.to_string());
+                return;
+            }
+        };
+
+        let Some(table) = self.mem_table() else { return };
+        let found = table.iter().position(|entry| match entry {
+            MemTableEntry::Free(space) => space.sectors >= needed_sectors,
+            MemTableEntry::Partition(_) => false,
+        });
+
+        match found {
+            Some(index) => self.table_selected = Some(index),
+            None => self.jump_error = Some(format!("no free region fits {text}")),
+        }
+    }
+
+    /// Zaps the current device's partition table and filesystem signatures
+    /// outright via `wipefs`/`sgdisk`, distinct from an in-memory "new
+    /// partition table" which only replaces `mem_table` until `apply`. Only
+    /// called once `text` (the user's confirmation input) matches the
+    /// device's exact path, given there's no undo for this one.
+    fn wipe_disk(&mut self, text: &str) {
+        self.wipe_error = None;
+        let Some(device) = self.devices.get(self.current_device) else { return };
+        let path = device.disk().path.clone();
+
+        if text.trim() != path.to_string_lossy() {
+            self.wipe_error = Some("confirmation text didn't match the device path; nothing was wiped".to_string());
+            return;
+        }
+
+        let result = Command::new("wipefs")
+            .arg("-a")
+            .arg(&path)
+            .run()
+            .and_then(|()| Command::new("sgdisk").arg("--zap-all").arg(&path).run());
+
+        match result {
+            Ok(()) => {
+                let mut disk = device.disk().clone();
+                disk.table_type = TableType::None;
+                self.devices[self.current_device] = Device::Incompatible(disk);
+                // The wipe already happened on real hardware, unlike a
+                // planned partition — so this is the new baseline `Ctrl+z`
+                // should discard back to, not the pre-wipe table.
+                self.original_devices[self.current_device] = self.devices[self.current_device].clone();
+                self.table_selected = None;
+                self.editor = DiskEditor::default();
+            }
+            Err(err) => self.wipe_error = Some(err.to_string()),
+        }
+    }
+
+    /// Converts the current `Device::Incompatible` into a `Device::Compatible`
+    /// with a blank in-memory GPT table, reserving one `editor::align_sectors`
+    /// unit at each end for the primary/backup GPT header and partition
+    /// array — the same alignment unit `editor` already reserves around
+    /// partition boundaries, close enough to the real ~1 MiB GPT overhead
+    /// without needing a full `gptman`-style layout just to plan free space.
+    ///
+    /// Becomes the new `original_devices` baseline immediately, same as a
+    /// real `wipe_disk`: `CompatDevice::is_dirty` only tracks planned
+    /// (non-real) partitions, so an empty table never reads as dirty
+    /// anyway — `Ctrl+z` afterward discards a partition planned on top of
+    /// this table, not the choice to create the table itself.
+    ///
+    /// Refuses a hybrid-MBR device (`Disk::hybrid_mbr`) even though it's
+    /// also `Incompatible` — creating a table over it here would silently
+    /// lie about being safe to edit when the real disk still has a
+    /// hand-crafted MBR nothing in this flow understands how to preserve.
+    fn create_partition_table(&mut self) {
+        let Some(Device::Incompatible(disk)) = self.devices.get(self.current_device) else { return };
+        if disk.hybrid_mbr {
+            return;
+        }
+        let mut disk = disk.clone();
+        let reserve = editor::align_sectors(disk.sector_size);
+        let total_sectors = disk.size / disk.sector_size;
+        disk.table_type = TableType::Gpt;
+        disk.starting_lba = reserve.saturating_sub(1);
+        disk.ending_lba = total_sectors.saturating_sub(reserve);
+        self.devices[self.current_device] = Device::Compatible(CompatDevice::new(disk, Vec::new()));
+        self.original_devices[self.current_device] = self.devices[self.current_device].clone();
+        self.table_selected = None;
+        self.editor = DiskEditor::default();
+    }
+
+    /// Copies the selected partition's exact size into the create prompt at
+    /// the first free region big enough to hold it, so making several
+    /// identically-sized partitions (RAID members, for example) doesn't mean
+    /// re-typing the size expression each time. Does nothing if the
+    /// selection isn't a partition or no free region fits.
+    fn duplicate_selected_size(&mut self) {
+        let Some(device) = self.devices.get(self.current_device) else { return };
+        let sector_size = device.disk().sector_size;
+
+        let Some(table) = self.mem_table() else { return };
+        let Some(selected) = self.table_selected else { return };
+        let Some(MemTableEntry::Partition(partition)) = table.get(selected) else { return };
+        let bytes = partition.sectors * sector_size;
+
+        let found = table.iter().position(|entry| match entry {
+            MemTableEntry::Free(space) => space.sectors * sector_size >= bytes,
+            MemTableEntry::Partition(_) => false,
+        });
+
+        if let Some(index) = found {
+            self.table_selected = Some(index);
+            self.editor.create_input.set(bytes.to_string());
+        }
+    }
+
+    /// The partition at the current table selection, if any — `None` for no
+    /// selection, a free region, or a device with no table at all.
+    fn selected_partition(&self) -> Option<&MemPartition> {
+        let table = self.mem_table()?;
+        match table.get(self.table_selected?)? {
+            MemTableEntry::Partition(partition) => Some(partition),
+            MemTableEntry::Free(_) => None,
+        }
+    }
+
+    /// Mutable counterpart to `selected_partition`, for writing back the
+    /// result of the GPT attribute-toggle popup.
+    fn selected_partition_mut(&mut self) -> Option<&mut MemPartition> {
+        let selected = self.table_selected?;
+        match self.devices.get_mut(self.current_device)? {
+            Device::Compatible(dev) => match dev.mem_table.get_mut(selected)? {
+                MemTableEntry::Partition(partition) => Some(partition),
+                MemTableEntry::Free(_) => None,
+            },
+            Device::Incompatible(_) => None,
+        }
+    }
+
+    /// Whether `G` should do anything right now: GPT attribute bits are a
+    /// GPT-only concept, so this refuses on an MBR (or tableless) device, and
+    /// like every other mutating action it's disabled in read-only mode.
+    fn can_edit_gpt_attributes(&self) -> bool {
+        !self.read_only
+            && self.devices.get(self.current_device).map(|d| d.disk().table_type) == Some(TableType::Gpt)
+            && self.selected_partition().is_some()
+    }
+
+    /// Opens the GPT attribute popup, seeded with the selected partition's
+    /// current flags.
+    fn open_gpt_attributes_editor(&mut self) {
+        let Some(partition) = self.selected_partition() else { return };
+        let attrs = partition.gpt_attributes;
+        self.gpt_attributes_editor = ToggleList::new(
+            vec![
+                ("Required (system partition)".to_string(), attrs.required),
+                ("No automount".to_string(), attrs.no_automount),
+                ("Legacy BIOS bootable".to_string(), attrs.legacy_bios_bootable),
+            ],
+            ToggleListArgs::default().title("GPT attributes — space to toggle, Enter to save".into()),
+        );
+        self.gpt_attributes_editor.selectable.select(Some(0));
+        self.show_gpt_attributes = true;
+    }
+
+    /// Writes `gpt_attributes_editor`'s current values back onto the selected
+    /// partition. Called on Enter; Esc discards the popup without calling
+    /// this, matching `alias_input`/`jump_input`'s cancel behavior.
+    fn apply_gpt_attributes(&mut self) {
+        let values = self.gpt_attributes_editor.values().to_vec();
+        let attrs = GptAttributes {
+            required: values.first().copied().unwrap_or(false),
+            no_automount: values.get(1).copied().unwrap_or(false),
+            legacy_bios_bootable: values.get(2).copied().unwrap_or(false),
+        };
+        if let Some(partition) = self.selected_partition_mut() {
+            partition.gpt_attributes = attrs;
+        }
+    }
+
+    /// Whether `s` should do anything right now: like every other mutating
+    /// action it's disabled in read-only mode, and there has to be a
+    /// partition selected to set a filesystem on.
+    fn can_set_filesystem(&self) -> bool {
+        !self.read_only && self.selected_partition().is_some()
+    }
+
+    /// Opens the filesystem popup, seeded with the selected partition's
+    /// current filesystem highlighted.
+    fn open_filesystem_editor(&mut self) {
+        let Some(partition) = self.selected_partition() else { return };
+        let current = partition.filesystem;
+        let filesystems = FileSystem::selectable();
+        let items = filesystems.iter().map(|fs| fs.as_ref().to_string()).collect();
+        self.filesystem_editor = SearchableMenu::new(items, MenuArgs::default().title("Set filesystem".into()));
+        self.filesystem_editor.select(filesystems.iter().position(|fs| *fs == current));
+        self.show_filesystem_editor = true;
+    }
+
+    /// Writes `filesystem_editor`'s current selection back onto the selected
+    /// partition. Called on Enter; Esc discards the popup without calling
+    /// this, matching `gpt_attributes_editor`'s cancel behavior.
+    fn apply_filesystem_editor(&mut self) {
+        let Some(label) = self.filesystem_editor.current_item().cloned() else { return };
+        let Some(filesystem) = FileSystem::selectable().iter().find(|fs| fs.as_ref() == label) else { return };
+        if let Some(partition) = self.selected_partition_mut() {
+            partition.filesystem = *filesystem;
+        }
+    }
+
+    /// Every mountpoint already set on another partition of the current
+    /// device, for `DiskEditor::handle_set_mountpoint`'s duplicate check.
+    /// Scoped to the current device, matching `generate_fstab`'s own scope —
+    /// each device's `mem_table` produces its own fstab entries.
+    fn other_mountpoints(&self) -> Vec<&str> {
+        let Some(table) = self.mem_table() else { return Vec::new() };
+        table
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| Some(*index) != self.table_selected)
+            .filter_map(|(_, entry)| match entry {
+                MemTableEntry::Partition(partition) => partition.mountpoint.as_deref(),
+                MemTableEntry::Free(_) => None,
+            })
+            .collect()
+    }
+
+    /// Whether `m` should do anything right now: like every other mutating
+    /// action it's disabled in read-only mode, and there has to be a
+    /// partition selected to set a mountpoint on.
+    fn can_set_mountpoint(&self) -> bool {
+        !self.read_only && self.selected_partition().is_some()
+    }
+
+    /// Opens the mountpoint prompt, prefilled with the selected partition's
+    /// current mountpoint if it has one.
+    fn open_mountpoint_editor(&mut self) {
+        let Some(partition) = self.selected_partition() else { return };
+        self.editor.mountpoint_input.set(partition.mountpoint.clone().unwrap_or_default());
+        self.show_mountpoint_editor = true;
+    }
+
+    /// Validates `text` with `DiskEditor::handle_set_mountpoint` and, on
+    /// success, writes it back onto the selected partition; on failure, sets
+    /// `mountpoint_error` instead of touching the partition.
+    fn apply_mountpoint_editor(&mut self, text: &str) {
+        let existing = self.other_mountpoints();
+        match self.editor.handle_set_mountpoint(text, &existing) {
+            Ok(mountpoint) => {
+                if let Some(partition) = self.selected_partition_mut() {
+                    partition.mountpoint = Some(mountpoint);
+                }
+            }
+            Err(err) => self.mountpoint_error = Some(err.to_string()),
+        }
+    }
+
+    /// Whether `l` should do anything right now: like every other mutating
+    /// action it's disabled in read-only mode, and there has to be a
+    /// partition selected to set a label on.
+    fn can_set_label(&self) -> bool {
+        !self.read_only && self.selected_partition().is_some()
+    }
+
+    /// Opens the label prompt, prefilled with the selected partition's
+    /// current label if it has one.
+    fn open_label_editor(&mut self) {
+        let Some(partition) = self.selected_partition() else { return };
+        self.editor.label_input.set(partition.label.clone().unwrap_or_default());
+        self.show_label_editor = true;
+    }
+
+    /// Validates `text` with `DiskEditor::handle_set_label` and, on success,
+    /// writes it back onto the selected partition; on failure, sets
+    /// `label_error` instead of touching the partition.
+    fn apply_label_editor(&mut self, text: &str) {
+        let Some(filesystem) = self.selected_partition().map(|p| p.filesystem) else { return };
+        match self.editor.handle_set_label(text, filesystem) {
+            Ok(label) => {
+                if let Some(partition) = self.selected_partition_mut() {
+                    partition.label = label;
+                }
+            }
+            Err(err) => self.label_error = Some(err.to_string()),
+        }
+    }
+}
+
+impl View for PartitionView {
+    fn render(&mut self, frame: &mut Frame, _config: &Config) {
+        let (body, hint) = split_body_and_hint(frame.size());
+        let mut title = "Partition disks".to_string();
+        if self.current_device_is_dirty() {
+            title.push_str(" *");
+        }
+        if self.read_only {
+            title.push_str(" [READ-ONLY]");
+        }
+        let block = Block::default().borders(Borders::ALL).title(title);
+
+        match self.mem_table() {
+            Some(table) if !table.is_empty() => {
+                let rows = self.partition_table_rows();
+                let table_widget = Table::new(rows)
+                    .header(Row::new(vec!["#", "Start", "End", "Size", "Filesystem"]))
+                    .block(block)
+                    .widths(&[
+                        Constraint::Percentage(8),
+                        Constraint::Percentage(23),
+                        Constraint::Percentage(23),
+                        Constraint::Percentage(23),
+                        Constraint::Percentage(23),
+                    ]);
+                frame.render_widget(table_widget, body);
+            }
+            _ => {
+                let text = match self.devices.get(self.current_device) {
+                    None => "No devices found.".to_string(),
+                    Some(device) => format!(
+                        "{} — {} device(s) detected. Press 'i' for details.",
+                        device_summary(device, self.alias_for(device)),
+                        self.devices.len()
+                    ),
+                };
+                frame.render_widget(Paragraph::new(Line::from(text)).block(block), body);
+            }
+        }
+        render_keybinding_hint(frame, hint, if self.read_only { READ_ONLY_TIP } else { PARTITION_TIP });
+
+        if self.show_details {
+            let area = centered_rect(50, 10, frame.size());
+            let block = Block::default().borders(Borders::ALL).title("Device details");
+            frame.render_widget(Clear, area);
+            frame.render_widget(Paragraph::new(self.details_text()).block(block), area);
+        }
+
+        if self.show_device_menu {
+            let area = centered_rect(50, 40, frame.size());
+            frame.render_widget(Clear, area);
+            self.device_menu.render(frame, area);
+        }
+
+        if self.show_jump_prompt {
+            let area = centered_rect(50, 3, frame.size());
+            frame.render_widget(Clear, area);
+            self.jump_input.render(frame, area);
+        }
+
+        if let Some(error) = &self.jump_error {
+            let area = centered_rect(50, 3, frame.size());
+            let block = Block::default().borders(Borders::ALL).title("No fit");
+            frame.render_widget(Clear, area);
+            frame.render_widget(Paragraph::new(Line::from(error.as_str())).block(block), area);
+        }
+
+        if let Some(note) = &self.status_note {
+            let area = centered_rect(50, 3, frame.size());
+            let block = Block::default().borders(Borders::ALL).title("Notice");
+            frame.render_widget(Clear, area);
+            frame.render_widget(Paragraph::new(Line::from(note.as_str())).block(block), area);
+        }
+
+        if self.show_discard_confirm {
+            let area = centered_rect(50, 3, frame.size());
+            let block = Block::default().borders(Borders::ALL).title("Discard all changes to this device?");
+            frame.render_widget(Clear, area);
+            frame.render_widget(Paragraph::new(Line::from("This can't be undone. y to confirm, any other key to cancel.")).block(block), area);
+        }
+
+        if self.show_alias_prompt {
+            let area = centered_rect(50, 3, frame.size());
+            frame.render_widget(Clear, area);
+            self.alias_input.render(frame, area);
+        }
+
+        if self.show_wipe_confirm {
+            let area = centered_rect(60, 3, frame.size());
+            frame.render_widget(Clear, area);
+            self.wipe_confirm_input.render(frame, area);
+        }
+
+        if let Some(error) = &self.wipe_error {
+            let area = centered_rect(60, 3, frame.size());
+            let block = Block::default().borders(Borders::ALL).title("Wipe failed");
+            frame.render_widget(Clear, area);
+            frame.render_widget(Paragraph::new(Line::from(error.as_str())).block(block), area);
+        }
+
+        if let Some(error) = &self.apply_error {
+            let area = centered_rect(60, 3, frame.size());
+            let block = Block::default().borders(Borders::ALL).title("Apply failed");
+            frame.render_widget(Clear, area);
+            frame.render_widget(Paragraph::new(Line::from(error.as_str())).block(block), area);
+        }
+
+        if self.show_leave_confirm {
+            let area = centered_rect(50, 3, frame.size());
+            let block = Block::default().borders(Borders::ALL).title("Unsaved changes — leave anyway? y/n");
+            frame.render_widget(Clear, area);
+            frame.render_widget(Paragraph::new(Line::from("")).block(block), area);
+        }
+
+        if self.show_gpt_attributes {
+            let area = centered_rect(50, 8, frame.size());
+            frame.render_widget(Clear, area);
+            self.gpt_attributes_editor.render(frame, area);
+        }
+
+        if self.show_filesystem_editor {
+            let area = centered_rect(50, 40, frame.size());
+            frame.render_widget(Clear, area);
+            self.filesystem_editor.render(frame, area);
+        }
+
+        if self.show_mountpoint_editor {
+            let area = centered_rect(50, 3, frame.size());
+            frame.render_widget(Clear, area);
+            self.editor.mountpoint_input.render(frame, area);
+        }
+
+        if let Some(error) = &self.mountpoint_error {
+            let area = centered_rect(50, 3, frame.size());
+            let block = Block::default().borders(Borders::ALL).title("Invalid mountpoint");
+            frame.render_widget(Clear, area);
+            frame.render_widget(Paragraph::new(Line::from(error.as_str())).block(block), area);
+        }
+
+        if self.show_label_editor {
+            let area = centered_rect(50, 3, frame.size());
+            frame.render_widget(Clear, area);
+            self.editor.label_input.render(frame, area);
+        }
+
+        if let Some(error) = &self.label_error {
+            let area = centered_rect(50, 3, frame.size());
+            let block = Block::default().borders(Borders::ALL).title("Invalid label");
+            frame.render_widget(Clear, area);
+            frame.render_widget(Paragraph::new(Line::from(error.as_str())).block(block), area);
+        }
+
+        if self.show_apply_confirm {
+            let rows = self.apply_preview_rows();
+            let area = centered_rect(70, (rows.len() as u16 + 4).min(20), frame.size());
+            let table = Table::new(rows)
+                .header(Row::new(vec!["#", "Start", "End", "Size", "Filesystem", "Status"]))
+                .block(Block::default().borders(Borders::ALL).title("Confirm write — y to apply, any other key to cancel"))
+                .widths(&[
+                    Constraint::Percentage(8),
+                    Constraint::Percentage(18),
+                    Constraint::Percentage(18),
+                    Constraint::Percentage(18),
+                    Constraint::Percentage(20),
+                    Constraint::Percentage(18),
+                ]);
+            frame.render_widget(Clear, area);
+            frame.render_widget(table, area);
+        }
+    }
+
+    fn on_event(&mut self, event: Event, config: &mut Config) -> Result<Option<Msg>> {
+        if let Event::Mouse(mouse) = &event {
+            if self.show_device_menu {
+                self.device_menu.handle_mouse(mouse);
+            }
+            return Ok(None);
+        }
+
+        if let Event::Key(key) = event {
+            if key.kind != KeyEventKind::Press {
+                return Ok(None);
+            }
+
+            if self.show_device_menu {
+                match key.code {
+                    KeyCode::Down | KeyCode::Char('j') => self.device_menu.selectable.select_next_item(),
+                    KeyCode::Up | KeyCode::Char('k') => self.device_menu.selectable.select_prev_item(),
+                    KeyCode::Enter => self.switch_device(),
+                    KeyCode::Esc => self.show_device_menu = false,
+                    _ => {}
+                }
+                return Ok(None);
+            }
+
+            if self.jump_error.is_some() {
+                self.jump_error = None;
+                return Ok(None);
+            }
+
+            if self.status_note.is_some() {
+                self.status_note = None;
+                return Ok(None);
+            }
+
+            if self.wipe_error.is_some() {
+                self.wipe_error = None;
+                return Ok(None);
+            }
+
+            if self.apply_error.is_some() {
+                self.apply_error = None;
+                return Ok(None);
+            }
+
+            if self.mountpoint_error.is_some() {
+                self.mountpoint_error = None;
+                return Ok(None);
+            }
+
+            if self.label_error.is_some() {
+                self.label_error = None;
+                return Ok(None);
+            }
+
+            if self.show_apply_confirm {
+                self.show_apply_confirm = false;
+                if key.code == KeyCode::Char('y') {
+                    if let Some(Device::Compatible(device)) = self.devices.get_mut(self.current_device) {
+                        let path = device.disk.path.to_string_lossy().into_owned();
+                        crate::logger::log_event("apply-confirmed", &[("path", &path)]);
+                        match crate::apply::apply_device(device, config.fstab_mode) {
+                            Ok(()) => {
+                                device.mark_applied();
+                                // Applied partitions are now really on disk, so that's
+                                // the new baseline `Ctrl+z` should discard back to.
+                                self.original_devices[self.current_device] = Device::Compatible(device.clone());
+                            }
+                            Err(err) => self.apply_error = Some(err.to_string()),
+                        }
+                    }
+                }
+                return Ok(None);
+            }
+
+            if self.show_leave_confirm {
+                self.show_leave_confirm = false;
+                if key.code == KeyCode::Char('y') {
+                    return Ok(Some(Msg::Pop));
+                }
+                return Ok(None);
+            }
+
+            if self.show_discard_confirm {
+                self.show_discard_confirm = false;
+                if key.code == KeyCode::Char('y') {
+                    self.discard_changes();
+                }
+                return Ok(None);
+            }
+
+            if self.show_wipe_confirm {
+                match self.wipe_confirm_input.on_event(&Event::Key(key)) {
+                    Some(InputCommand::Submit) => {
+                        let text = self.wipe_confirm_input.take();
+                        self.show_wipe_confirm = false;
+                        self.wipe_disk(&text);
+                    }
+                    Some(InputCommand::Cancel | InputCommand::Empty) => {
+                        self.wipe_confirm_input.take();
+                        self.show_wipe_confirm = false;
+                    }
+                    None => {}
+                }
+                return Ok(None);
+            }
+
+            if self.show_alias_prompt {
+                match self.alias_input.on_event(&Event::Key(key)) {
+                    Some(InputCommand::Submit) => {
+                        let text = self.alias_input.take();
+                        self.show_alias_prompt = false;
+                        self.set_alias(&text);
+                    }
+                    Some(InputCommand::Cancel | InputCommand::Empty) => {
+                        self.alias_input.take();
+                        self.show_alias_prompt = false;
+                    }
+                    None => {}
+                }
+                return Ok(None);
+            }
+
+            if self.show_gpt_attributes {
+                match key.code {
+                    KeyCode::Down | KeyCode::Char('j') => self.gpt_attributes_editor.selectable.select_next_item(),
+                    KeyCode::Up | KeyCode::Char('k') => self.gpt_attributes_editor.selectable.select_prev_item(),
+                    KeyCode::Char(' ') => self.gpt_attributes_editor.toggle_selected(),
+                    KeyCode::Enter => {
+                        self.apply_gpt_attributes();
+                        self.show_gpt_attributes = false;
+                    }
+                    KeyCode::Esc => self.show_gpt_attributes = false,
+                    _ => {}
+                }
+                return Ok(None);
+            }
+
+            if self.show_filesystem_editor {
+                match self.filesystem_editor.on_event(&Event::Key(key)) {
+                    Some(true) => {
+                        self.apply_filesystem_editor();
+                        self.show_filesystem_editor = false;
+                    }
+                    Some(false) => self.show_filesystem_editor = false,
+                    None => {}
+                }
+                return Ok(None);
+            }
+
+            if self.show_mountpoint_editor {
+                match self.editor.mountpoint_input.on_event(&Event::Key(key)) {
+                    Some(InputCommand::Submit) => {
+                        let text = self.editor.mountpoint_input.take();
+                        self.show_mountpoint_editor = false;
+                        self.apply_mountpoint_editor(&text);
+                    }
+                    Some(InputCommand::Cancel | InputCommand::Empty) => {
+                        self.editor.mountpoint_input.take();
+                        self.show_mountpoint_editor = false;
+                    }
+                    None => {}
+                }
+                return Ok(None);
+            }
+
+            if self.show_label_editor {
+                match self.editor.label_input.on_event(&Event::Key(key)) {
+                    Some(InputCommand::Submit) => {
+                        let text = self.editor.label_input.take();
+                        self.show_label_editor = false;
+                        self.apply_label_editor(&text);
+                    }
+                    Some(InputCommand::Cancel | InputCommand::Empty) => {
+                        self.editor.label_input.take();
+                        self.show_label_editor = false;
+                    }
+                    None => {}
+                }
+                return Ok(None);
+            }
+
+            if self.show_jump_prompt {
+                match self.jump_input.on_event(&Event::Key(key)) {
+                    Some(InputCommand::Submit) => {
+                        let text = self.jump_input.take();
+                        self.show_jump_prompt = false;
+                        self.jump_to_fitting_free_space(&text);
+                    }
+                    Some(InputCommand::Cancel | InputCommand::Empty) => {
+                        self.jump_input.take();
+                        self.show_jump_prompt = false;
+                    }
+                    None => {}
+                }
+                return Ok(None);
+            }
+
+            match key.code {
+                KeyCode::Char('i') => self.show_details = !self.show_details,
+                KeyCode::Char('d') => self.open_device_menu(),
+                KeyCode::Char('a') => self.show_alias_prompt = true,
+                KeyCode::Char('f') => self.show_jump_prompt = true,
+                KeyCode::Char('L') => self.jump_to_largest_free_region(),
+                KeyCode::Char('n') if !self.read_only => self.create_partition_table(),
+                KeyCode::Char('y') if !self.read_only => self.duplicate_selected_size(),
+                KeyCode::Char('G') if self.can_edit_gpt_attributes() => self.open_gpt_attributes_editor(),
+                KeyCode::Char('s') if self.can_set_filesystem() => self.open_filesystem_editor(),
+                KeyCode::Char('m') if self.can_set_mountpoint() => self.open_mountpoint_editor(),
+                KeyCode::Char('l') if self.can_set_label() => self.open_label_editor(),
+                KeyCode::Char('W') if !self.read_only => self.show_wipe_confirm = true,
+                KeyCode::Char('A') if !self.read_only => self.show_apply_confirm = true,
+                KeyCode::Char('z') if !self.read_only && key.modifiers.contains(KeyModifiers::CONTROL) => {
+                    if self.current_device_is_dirty() {
+                        self.show_discard_confirm = true;
+                    }
+                }
+                KeyCode::Char('R') => self.read_only = !self.read_only,
+                KeyCode::Char('v') => {
+                    return Ok(Some(Msg::Push(Box::new(AllPartitions::new(self.devices.clone())))))
+                }
+                KeyCode::Esc if self.show_details => self.show_details = false,
+                KeyCode::Esc if self.current_device_is_dirty() => self.show_leave_confirm = true,
+                KeyCode::Esc => return Ok(Some(Msg::Pop)),
+                KeyCode::Char('}') | KeyCode::Char('{') => self.handle_table(key.code),
+                _ => {}
+            }
+        }
+        Ok(None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tui::data::partition::{CompatDevice, Disk, FileSystem, GptAttributes, MemPartition, TableType};
+
+    fn disk(sector_size: u64) -> Disk {
+        Disk {
+            path: "/dev/sda".into(),
+            model: "Test Disk".into(),
+            size: 4_000_000_000,
+            sector_size,
+            table_type: TableType::Gpt,
+            starting_lba: 33,
+            ending_lba: 4_000_000_000 / sector_size - 33,
+            hybrid_mbr: false,
+        }
+    }
+
+    #[test]
+    fn common_512_byte_sectors_get_no_note() {
+        assert_eq!(sector_size_note(512), "");
+    }
+
+    #[test]
+    fn a_4kn_disk_notes_its_sector_size() {
+        assert_eq!(sector_size_note(4096), ", 4096-byte sectors");
+    }
+
+    #[test]
+    fn device_summary_carries_the_4kn_note_through() {
+        let device = Device::Incompatible(disk(4096));
+        let summary = device_summary(&device, None);
+        assert!(summary.contains("4096-byte sectors"), "summary was: {summary}");
+    }
+
+    #[test]
+    fn device_summary_omits_the_note_for_a_512_byte_disk() {
+        let device = Device::Incompatible(disk(512));
+        let summary = device_summary(&device, None);
+        assert!(!summary.contains("byte sectors"), "summary was: {summary}");
+    }
+
+    #[test]
+    fn alignment_loss_text_is_empty_when_the_region_ends_on_an_aligned_boundary() {
+        let mem_table = vec![MemTableEntry::Free(crate::tui::data::partition::DiskSpace { start: 4096, sectors: 6144 })];
+        let mut view = PartitionView::new(vec![Device::Compatible(CompatDevice { disk: disk(512), mem_table })]);
+        view.table_selected = Some(0);
+
+        assert_eq!(view.alignment_loss_text(), "");
+    }
+
+    #[test]
+    fn alignment_loss_text_reports_the_sectors_reserved_for_alignment() {
+        let mem_table = vec![MemTableEntry::Free(crate::tui::data::partition::DiskSpace { start: 4096, sectors: 2500 })];
+        let mut view = PartitionView::new(vec![Device::Compatible(CompatDevice { disk: disk(512), mem_table })]);
+        view.table_selected = Some(0);
+
+        let text = view.alignment_loss_text();
+        assert!(text.contains("452 sector(s)"), "text was: {text}");
+    }
+
+    #[test]
+    fn alignment_loss_text_is_empty_for_a_selected_partition_row() {
+        let mem_table = vec![MemTableEntry::Partition(MemPartition {
+            number: 1,
+            start: 2048,
+            sectors: 1000,
+            filesystem: FileSystem::Fat32,
+            label: None,
+            mountpoint: None,
+            mkfs_options: None,
+            gpt_attributes: GptAttributes::default(),
+            real: false,
+        })];
+        let mut view = PartitionView::new(vec![Device::Compatible(CompatDevice { disk: disk(512), mem_table })]);
+        view.table_selected = Some(0);
+
+        assert_eq!(view.alignment_loss_text(), "");
+    }
+
+    #[test]
+    fn jump_to_largest_free_region_selects_the_biggest_gap_and_notes_its_size() {
+        let mem_table = vec![
+            MemTableEntry::Free(crate::tui::data::partition::DiskSpace { start: 34, sectors: 100 }),
+            MemTableEntry::Partition(MemPartition {
+                number: 1,
+                start: 134,
+                sectors: 200,
+                filesystem: FileSystem::Fat32,
+                label: None,
+                mountpoint: None,
+                mkfs_options: None,
+                gpt_attributes: GptAttributes::default(),
+                real: false,
+            }),
+            MemTableEntry::Free(crate::tui::data::partition::DiskSpace { start: 334, sectors: 5000 }),
+        ];
+        let mut view = PartitionView::new(vec![Device::Compatible(CompatDevice { disk: disk(512), mem_table })]);
+
+        view.jump_to_largest_free_region();
+
+        assert_eq!(view.table_selected, Some(2));
+        assert!(view.status_note.is_some());
+    }
+
+    #[test]
+    fn jump_to_largest_free_region_is_a_no_op_with_no_free_space() {
+        let mem_table = vec![MemTableEntry::Partition(MemPartition {
+            number: 1,
+            start: 34,
+            sectors: 200,
+            filesystem: FileSystem::Fat32,
+            label: None,
+            mountpoint: None,
+            mkfs_options: None,
+            gpt_attributes: GptAttributes::default(),
+            real: false,
+        })];
+        let mut view = PartitionView::new(vec![Device::Compatible(CompatDevice { disk: disk(512), mem_table })]);
+
+        view.jump_to_largest_free_region();
+
+        assert_eq!(view.table_selected, None);
+        assert!(view.status_note.is_none());
+    }
+
+    #[test]
+    fn apply_preview_lists_only_partitions_with_exact_sector_ranges() {
+        let mem_table = vec![
+            MemTableEntry::Partition(MemPartition {
+                number: 1,
+                start: 2048,
+                sectors: 1000,
+                filesystem: FileSystem::Fat32,
+                label: None,
+                mountpoint: None,
+                mkfs_options: None,
+                gpt_attributes: GptAttributes::default(),
+                real: false,
+            }),
+            MemTableEntry::Free(crate::tui::data::partition::DiskSpace { start: 3048, sectors: 500 }),
+        ];
+        let view = PartitionView::new(vec![Device::Compatible(CompatDevice { disk: disk(512), mem_table })]);
+
+        let rows = view.apply_preview_rows();
+        assert_eq!(rows.len(), 1);
+    }
+
+    #[test]
+    fn a_planned_partition_marks_the_current_device_dirty() {
+        let mem_table = vec![MemTableEntry::Partition(MemPartition {
+            number: 1,
+            start: 2048,
+            sectors: 1000,
+            filesystem: FileSystem::Ext4,
+            label: None,
+            mountpoint: None,
+            mkfs_options: None,
+            gpt_attributes: GptAttributes::default(),
+            real: false,
+        })];
+        let view = PartitionView::new(vec![Device::Compatible(CompatDevice { disk: disk(512), mem_table })]);
+        assert!(view.current_device_is_dirty());
+    }
+
+    #[test]
+    fn an_incompatible_device_is_never_dirty() {
+        let view = PartitionView::new(vec![Device::Incompatible(disk(512))]);
+        assert!(!view.current_device_is_dirty());
+    }
+
+    #[test]
+    fn discard_changes_reverts_to_the_enumerated_snapshot() {
+        let mem_table = vec![MemTableEntry::Partition(MemPartition {
+            number: 1,
+            start: 2048,
+            sectors: 1000,
+            filesystem: FileSystem::Fat32,
+            label: None,
+            mountpoint: None,
+            mkfs_options: None,
+            gpt_attributes: GptAttributes::default(),
+            real: true,
+        })];
+        let mut view = PartitionView::new(vec![Device::Compatible(CompatDevice { disk: disk(512), mem_table })]);
+        assert!(!view.current_device_is_dirty());
+
+        // Simulate planning a new partition after the device was enumerated.
+        if let Some(Device::Compatible(dev)) = view.devices.get_mut(0) {
+            dev.mem_table.push(MemTableEntry::Partition(MemPartition {
+                number: 2,
+                start: 3048,
+                sectors: 500,
+                filesystem: FileSystem::Ext4,
+                label: None,
+                mountpoint: None,
+                mkfs_options: None,
+                gpt_attributes: GptAttributes::default(),
+                real: false,
+            }));
+        }
+        assert!(view.current_device_is_dirty());
+
+        view.discard_changes();
+
+        assert!(!view.current_device_is_dirty());
+    }
+
+    #[test]
+    fn set_devices_clamps_current_device_when_the_list_shrinks() {
+        let mut second = disk(512);
+        second.path = "/dev/sdb".into();
+        let mut view =
+            PartitionView::new(vec![Device::Incompatible(disk(512)), Device::Incompatible(second)]);
+        view.current_device = 1;
+
+        view.set_devices(vec![Device::Incompatible(disk(512))]);
+
+        assert_eq!(view.current_device, 0);
+        assert_eq!(view.devices.len(), 1);
+    }
+
+    #[test]
+    fn set_devices_clamps_to_zero_when_the_list_becomes_empty() {
+        let mut view = PartitionView::new(vec![Device::Incompatible(disk(512))]);
+
+        view.set_devices(Vec::new());
+
+        assert_eq!(view.current_device, 0);
+        assert!(view.mem_table().is_none());
+    }
+
+    #[test]
+    fn select_device_by_path_switches_to_the_matching_device() {
+        let mut second = disk(512);
+        second.path = "/dev/sdb".into();
+        let mut view = PartitionView::new(vec![Device::Incompatible(disk(512)), Device::Incompatible(second)]);
+
+        assert!(view.select_device_by_path(std::path::Path::new("/dev/sdb")));
+        assert_eq!(view.current_device, 1);
+    }
+
+    #[test]
+    fn select_device_by_path_with_no_match_leaves_the_selection_untouched() {
+        let mut view = PartitionView::new(vec![Device::Incompatible(disk(512))]);
+
+        assert!(!view.select_device_by_path(std::path::Path::new("/dev/nonexistent")));
+        assert_eq!(view.current_device, 0);
+    }
+
+    fn planned_partition() -> MemPartition {
+        MemPartition {
+            number: 1,
+            start: 34,
+            sectors: 100,
+            filesystem: FileSystem::Ext4,
+            label: None,
+            mountpoint: None,
+            mkfs_options: None,
+            gpt_attributes: GptAttributes::default(),
+            real: false,
+        }
+    }
+
+    #[test]
+    fn free_space_is_styled_green() {
+        let entry = MemTableEntry::Free(crate::tui::data::partition::DiskSpace { start: 34, sectors: 100 });
+        assert_eq!(PartitionView::row_style(&entry), Style::default().fg(Color::Green));
+    }
+
+    #[test]
+    fn a_planned_partition_is_styled_cyan() {
+        let entry = MemTableEntry::Partition(planned_partition());
+        assert_eq!(PartitionView::row_style(&entry), Style::default().fg(Color::Cyan));
+    }
+
+    #[test]
+    fn a_real_partition_with_an_unknown_filesystem_is_styled_red() {
+        let entry = MemTableEntry::Partition(MemPartition {
+            filesystem: FileSystem::Unknown,
+            gpt_attributes: GptAttributes::default(),
+            real: true,
+            ..planned_partition()
+        });
+        assert_eq!(PartitionView::row_style(&entry), Style::default().fg(Color::Red));
+    }
+
+    #[test]
+    fn a_real_partition_with_a_known_filesystem_uses_the_default_style() {
+        let entry = MemTableEntry::Partition(MemPartition { real: true, ..planned_partition() });
+        assert_eq!(PartitionView::row_style(&entry), Style::default());
+    }
+
+    #[test]
+    fn create_partition_table_turns_an_incompatible_device_compatible() {
+        let mut view = PartitionView::new(vec![Device::Incompatible(disk(512))]);
+
+        view.create_partition_table();
+
+        assert!(matches!(view.devices[0], Device::Compatible(_)));
+        assert!(matches!(view.original_devices[0], Device::Compatible(_)));
+        assert_eq!(view.devices[0].disk().table_type, TableType::Gpt);
+        // No partitions planned yet, so the fresh table isn't flagged dirty.
+        assert!(!view.current_device_is_dirty());
+    }
+
+    #[test]
+    fn create_partition_table_refuses_a_hybrid_mbr_device() {
+        let mut hybrid = disk(512);
+        hybrid.hybrid_mbr = true;
+        let mut view = PartitionView::new(vec![Device::Incompatible(hybrid)]);
+
+        view.create_partition_table();
+
+        assert!(matches!(view.devices[0], Device::Incompatible(_)));
+    }
+
+    #[test]
+    fn create_partition_table_is_a_no_op_on_an_already_compatible_device() {
+        let mut view = PartitionView::new(vec![Device::Compatible(CompatDevice::new(disk(512), Vec::new()))]);
+
+        view.create_partition_table();
+
+        assert!(matches!(view.devices[0], Device::Compatible(_)));
+    }
+
+    #[test]
+    fn secure_boot_text_reports_enabled_disabled_and_unknown() {
+        assert_eq!(PartitionView::secure_boot_text(Some(true)), "\nSecure Boot: enabled");
+        assert_eq!(PartitionView::secure_boot_text(Some(false)), "\nSecure Boot: disabled");
+        assert_eq!(PartitionView::secure_boot_text(None), "\nSecure Boot: unknown (not UEFI, or undetectable)");
+    }
+
+    #[test]
+    fn partition_table_rows_covers_every_mem_table_entry_including_free_space() {
+        // `CompatDevice::new` fills the gap around the one planned partition
+        // with `MemTableEntry::Free` entries, so this exercises both branches
+        // of `partition_table_rows` without constructing `mem_table` by hand.
+        let view = PartitionView::new(vec![Device::Compatible(CompatDevice::new(disk(512), vec![planned_partition()]))]);
+
+        let table = view.mem_table().unwrap();
+        assert!(table.iter().any(|entry| matches!(entry, MemTableEntry::Free(_))));
+        assert!(table.iter().any(|entry| matches!(entry, MemTableEntry::Partition(_))));
+        assert_eq!(view.partition_table_rows().len(), table.len());
+    }
+
+    #[test]
+    fn can_edit_gpt_attributes_requires_a_gpt_device_and_a_selected_partition() {
+        let mut view = PartitionView::new(vec![Device::Compatible(CompatDevice::new(disk(512), vec![planned_partition()]))]);
+        assert!(!view.can_edit_gpt_attributes());
+
+        view.table_selected = Some(0);
+        assert!(view.can_edit_gpt_attributes());
+
+        view.read_only = true;
+        assert!(!view.can_edit_gpt_attributes());
+    }
+
+    #[test]
+    fn can_edit_gpt_attributes_refuses_an_mbr_device() {
+        let mut mbr_disk = disk(512);
+        mbr_disk.table_type = TableType::Mbr;
+        let mut view = PartitionView::new(vec![Device::Compatible(CompatDevice::new(mbr_disk, vec![planned_partition()]))]);
+        view.table_selected = Some(0);
+
+        assert!(!view.can_edit_gpt_attributes());
+    }
+
+    #[test]
+    fn open_gpt_attributes_editor_seeds_the_toggles_from_the_selected_partition() {
+        let mut partition = planned_partition();
+        partition.gpt_attributes = GptAttributes { required: true, no_automount: false, legacy_bios_bootable: true };
+        let mut view = PartitionView::new(vec![Device::Compatible(CompatDevice::new(disk(512), vec![partition]))]);
+        view.table_selected = Some(0);
+
+        view.open_gpt_attributes_editor();
+
+        assert_eq!(view.gpt_attributes_editor.values(), &[true, false, true]);
+    }
+
+    #[test]
+    fn apply_gpt_attributes_writes_the_toggled_values_back_to_the_partition() {
+        let mut view = PartitionView::new(vec![Device::Compatible(CompatDevice::new(disk(512), vec![planned_partition()]))]);
+        view.table_selected = Some(0);
+        view.open_gpt_attributes_editor();
+        view.gpt_attributes_editor.selectable.select(Some(1));
+        view.gpt_attributes_editor.toggle_selected();
+
+        view.apply_gpt_attributes();
+
+        assert!(view.selected_partition().unwrap().gpt_attributes.no_automount);
+    }
+
+    #[test]
+    fn can_set_filesystem_requires_a_selected_partition_and_refuses_read_only() {
+        let mut view = PartitionView::new(vec![Device::Compatible(CompatDevice::new(disk(512), vec![planned_partition()]))]);
+        assert!(!view.can_set_filesystem());
+
+        view.table_selected = Some(0);
+        assert!(view.can_set_filesystem());
+
+        view.read_only = true;
+        assert!(!view.can_set_filesystem());
+    }
+
+    #[test]
+    fn swap_is_offered_among_the_filesystem_choices() {
+        let mut view = PartitionView::new(vec![Device::Compatible(CompatDevice::new(disk(512), vec![planned_partition()]))]);
+        view.table_selected = Some(0);
+
+        view.open_filesystem_editor();
+
+        let swap_index = FileSystem::selectable().iter().position(|fs| *fs == FileSystem::Swap).unwrap();
+        view.filesystem_editor.select(Some(swap_index));
+        assert_eq!(view.filesystem_editor.current_item(), Some(&"Swap".to_string()));
+    }
+
+    #[test]
+    fn selecting_a_filesystem_writes_it_back_to_the_partition() {
+        let mut view = PartitionView::new(vec![Device::Compatible(CompatDevice::new(disk(512), vec![planned_partition()]))]);
+        view.table_selected = Some(0);
+        view.open_filesystem_editor();
+
+        let swap_index = FileSystem::selectable().iter().position(|fs| *fs == FileSystem::Swap).unwrap();
+        view.filesystem_editor.select(Some(swap_index));
+        view.apply_filesystem_editor();
+
+        assert_eq!(view.selected_partition().unwrap().filesystem, FileSystem::Swap);
+    }
+
+    #[test]
+    fn gpt_attributes_text_is_empty_until_a_flag_is_set() {
+        let mut view = PartitionView::new(vec![Device::Compatible(CompatDevice::new(disk(512), vec![planned_partition()]))]);
+        view.table_selected = Some(0);
+        assert_eq!(view.gpt_attributes_text(), "");
+
+        view.selected_partition_mut().unwrap().gpt_attributes.required = true;
+        assert_eq!(view.gpt_attributes_text(), "\nGPT attributes: required");
+    }
+
+    #[test]
+    fn can_set_mountpoint_requires_a_selected_partition_and_refuses_read_only() {
+        let mut view = PartitionView::new(vec![Device::Compatible(CompatDevice::new(disk(512), vec![planned_partition()]))]);
+        assert!(!view.can_set_mountpoint());
+
+        view.table_selected = Some(0);
+        assert!(view.can_set_mountpoint());
+
+        view.read_only = true;
+        assert!(!view.can_set_mountpoint());
+    }
+
+    #[test]
+    fn setting_a_valid_mountpoint_writes_it_back_to_the_partition() {
+        let mut view = PartitionView::new(vec![Device::Compatible(CompatDevice::new(disk(512), vec![planned_partition()]))]);
+        view.table_selected = Some(0);
+        view.open_mountpoint_editor();
+
+        view.apply_mountpoint_editor("/mnt/data");
+
+        assert_eq!(view.selected_partition().unwrap().mountpoint.as_deref(), Some("/mnt/data"));
+        assert!(view.mountpoint_error.is_none());
+    }
+
+    #[test]
+    fn setting_a_relative_mountpoint_reports_an_error_without_touching_the_partition() {
+        let mut view = PartitionView::new(vec![Device::Compatible(CompatDevice::new(disk(512), vec![planned_partition()]))]);
+        view.table_selected = Some(0);
+        view.open_mountpoint_editor();
+
+        view.apply_mountpoint_editor("mnt/data");
+
+        assert!(view.selected_partition().unwrap().mountpoint.is_none());
+        assert!(view.mountpoint_error.is_some());
+    }
+
+    #[test]
+    fn setting_a_mountpoint_already_used_by_another_partition_is_rejected() {
+        let mut view = PartitionView::new(vec![Device::Compatible(CompatDevice::new(
+            disk(512),
+            vec![
+                MemPartition { mountpoint: Some("/boot".to_string()), ..planned_partition() },
+                MemPartition { number: 2, start: 200, ..planned_partition() },
+            ],
+        ))]);
+        view.table_selected = Some(1);
+        view.open_mountpoint_editor();
+
+        view.apply_mountpoint_editor("/boot");
+
+        assert!(view.mountpoint_error.is_some());
+    }
+
+    #[test]
+    fn can_set_label_requires_a_selected_partition_and_refuses_read_only() {
+        let mut view = PartitionView::new(vec![Device::Compatible(CompatDevice::new(disk(512), vec![planned_partition()]))]);
+        assert!(!view.can_set_label());
+
+        view.table_selected = Some(0);
+        assert!(view.can_set_label());
+
+        view.read_only = true;
+        assert!(!view.can_set_label());
+    }
+
+    #[test]
+    fn setting_a_valid_label_writes_it_back_to_the_partition() {
+        let mut view = PartitionView::new(vec![Device::Compatible(CompatDevice::new(disk(512), vec![planned_partition()]))]);
+        view.table_selected = Some(0);
+        view.open_label_editor();
+
+        view.apply_label_editor("data");
+
+        assert_eq!(view.selected_partition().unwrap().label.as_deref(), Some("data"));
+        assert!(view.label_error.is_none());
+    }
+
+    #[test]
+    fn clearing_the_label_field_sets_no_label() {
+        let mut view = PartitionView::new(vec![Device::Compatible(CompatDevice::new(
+            disk(512),
+            vec![MemPartition { label: Some("old".to_string()), ..planned_partition() }],
+        ))]);
+        view.table_selected = Some(0);
+        view.open_label_editor();
+
+        view.apply_label_editor("");
+
+        assert!(view.selected_partition().unwrap().label.is_none());
+        assert!(view.label_error.is_none());
+    }
+
+    #[test]
+    fn setting_a_label_longer_than_the_filesystem_limit_is_rejected() {
+        let mut view = PartitionView::new(vec![Device::Compatible(CompatDevice::new(disk(512), vec![planned_partition()]))]);
+        view.table_selected = Some(0);
+        view.open_label_editor();
+
+        let too_long = "x".repeat(200);
+        view.apply_label_editor(&too_long);
+
+        assert!(view.selected_partition().unwrap().label.is_none());
+        assert!(view.label_error.is_some());
+    }
+}
@@ -0,0 +1,554 @@
+use anyhow::{Context, Result};
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    text::Line,
+    widgets::{Block, Borders, Paragraph},
+    Frame,
+};
+
+use crate::tui::data::partition::{parse_mkfs_options, ByteSize, DiskSpace, FileSystem};
+use crate::tui::widgets::input::{EmptyEnter, Input, InputArgs};
+use crate::tui::widgets::menu::searchable::SearchableMenu;
+use crate::tui::widgets::menu::MenuArgs;
+
+/// Standard partition alignment used by `parted`/modern installers, so a
+/// trailing reservation doesn't leave the created partition (or the
+/// reserved gap it produces) starting mid-way through an alignment unit.
+const DEFAULT_ALIGN_BYTES: u64 = 1024 * 1024;
+
+/// `DEFAULT_ALIGN_BYTES` expressed in sectors for a given `sector_size`.
+///
+/// `pub(super)` so the parent `partition` view can explain, in the details
+/// popup, how many sectors of a selected free region alignment would
+/// reserve if a partition were created to fill it.
+pub(super) fn align_sectors(sector_size: u64) -> u64 {
+    (DEFAULT_ALIGN_BYTES / sector_size).max(1)
+}
+
+/// `sectors * percent / 100`, rounded down. Shared by `handle_create`'s `%`
+/// form and its `half`/`quarter` shorthands, so all three go through
+/// identical math.
+fn percent_of_sectors(sectors: u64, percent: f64) -> u64 {
+    ((sectors as f64) * percent / 100.0) as u64
+}
+
+/// How many previous create-size entries `DiskEditor::size_history` keeps.
+/// A planning session rarely needs more than a handful of recalls, and
+/// capping it keeps the ring buffer from growing unbounded across a long
+/// session of creating many partitions.
+const SIZE_HISTORY_CAPACITY: usize = 20;
+
+/// Which way `DiskEditor::recall_size_history` steps through
+/// `size_history` — named after the direction in time rather than Up/Down
+/// so the mapping from arrow keys lives at the (not yet wired) call site,
+/// not here.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HistoryDirection {
+    /// Toward earlier entries — Up, in the usual shell-history binding.
+    Older,
+    /// Back toward the present, and eventually the user's own uncommitted
+    /// typing — Down.
+    Newer,
+}
+
+/// The create/delete/inspect panel shown alongside the partition table.
+pub struct DiskEditor {
+    pub create_input: Input,
+    /// Filesystem-type picker for the create flow. `SearchableMenu` over a
+    /// `Menu` for consistency with every other selection in the app, even
+    /// though `FileSystem::selectable` is short today — future filesystems
+    /// slot in without a UI change.
+    pub filesystem_picker: SearchableMenu,
+    /// Extra `mkfs.<fs>` flags for the partition being created, e.g. `-m 0`
+    /// for ext4 or `-n 32k` for btrfs. Optional — left empty, the filesystem
+    /// is formatted with just its base flags. Validated by
+    /// [`Self::mkfs_options`], not on every keystroke, so a user can type a
+    /// space-separated flag list without the field complaining mid-word.
+    pub mkfs_options_input: Input,
+    /// Path typed for "Set mountpoint" — validated by
+    /// [`Self::handle_set_mountpoint`] before it's stored on the selected
+    /// `MemPartition`.
+    pub mountpoint_input: Input,
+    /// Volume label typed for "Set label" — validated by
+    /// [`Self::handle_set_label`] before it's stored on the selected
+    /// `MemPartition`. Prefilled from the partition's current label when the
+    /// action is opened, so clearing it is just a select-all-and-delete
+    /// rather than needing a separate "clear" binding.
+    pub label_input: Input,
+    /// Previously-submitted `create_input` strings, oldest first, recalled
+    /// with `recall_size_history` — shell-history behavior for the size
+    /// prompt so planning several similarly-sized partitions doesn't mean
+    /// re-typing the same size expression each time.
+    size_history: Vec<String>,
+    /// Position while cycling `size_history`. `None` means the field is
+    /// showing the user's own typing rather than a recalled entry — the
+    /// starting state, and where `Newer` eventually lands after stepping
+    /// back past the most recent entry.
+    history_cursor: Option<usize>,
+}
+
+impl Default for DiskEditor {
+    fn default() -> Self {
+        let items = FileSystem::selectable().iter().map(|fs| fs.as_ref().to_string()).collect();
+        Self {
+            create_input: Input::default(),
+            filesystem_picker: SearchableMenu::new(items, MenuArgs::default().title("Filesystem".into())),
+            mkfs_options_input: Input::new(
+                InputArgs::default()
+                    .title("mkfs options (optional)".into())
+                    .on_empty_enter(EmptyEnter::SubmitEmpty)
+                    .placeholder("none".into()),
+            ),
+            mountpoint_input: Input::new(InputArgs::default().title("Mountpoint".into())),
+            label_input: Input::new(
+                InputArgs::default().title("Filesystem label".into()).on_empty_enter(EmptyEnter::SubmitEmpty),
+            ),
+            size_history: Vec::new(),
+            history_cursor: None,
+        }
+    }
+}
+
+impl DiskEditor {
+    /// The filesystem currently highlighted in `filesystem_picker`, or the
+    /// first selectable filesystem if nothing's been picked yet.
+    pub fn selected_filesystem(&self) -> FileSystem {
+        self.filesystem_picker
+            .current_item()
+            .and_then(|label| FileSystem::selectable().iter().find(|fs| fs.as_ref() == label))
+            .copied()
+            .unwrap_or(FileSystem::Ext4)
+    }
+
+    /// Renders the filesystem picker with its one-line description legend
+    /// and the optional mkfs-options field underneath, matching the
+    /// keyboard/locale/timezone picker UX.
+    pub fn render_filesystem_picker(&mut self, frame: &mut Frame, area: Rect) {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(0), Constraint::Length(1), Constraint::Length(3)])
+            .split(area);
+        self.filesystem_picker.render(frame, chunks[0]);
+        let block = Block::default().borders(Borders::NONE);
+        let legend = Paragraph::new(Line::from(self.selected_filesystem().description())).block(block);
+        frame.render_widget(legend, chunks[1]);
+        self.mkfs_options_input.render(frame, chunks[2]);
+    }
+
+    /// Validates `mkfs_options_input`'s contents via `parse_mkfs_options` and
+    /// returns the trimmed string to store on the new `MemPartition`, or
+    /// `None` for an empty field (the common case: no extra flags wanted).
+    pub fn mkfs_options(&self) -> Result<Option<String>> {
+        let text = self.mkfs_options_input.as_str().trim();
+        if text.is_empty() {
+            return Ok(None);
+        }
+        parse_mkfs_options(text)?;
+        Ok(Some(text.to_string()))
+    }
+
+    /// Records a just-submitted, non-empty `create_input` string into
+    /// `size_history`, deduplicating an immediate repeat so mashing Enter on
+    /// the same size doesn't spam the recall list. Resets the recall cursor
+    /// — a fresh submission always starts a new browsing session, not a
+    /// continuation of the last one.
+    pub fn push_size_history(&mut self, text: &str) {
+        if text.is_empty() {
+            return;
+        }
+        if self.size_history.last().map(String::as_str) != Some(text) {
+            self.size_history.push(text.to_string());
+            if self.size_history.len() > SIZE_HISTORY_CAPACITY {
+                self.size_history.remove(0);
+            }
+        }
+        self.history_cursor = None;
+    }
+
+    /// Recalls the previous/next `size_history` entry into `create_input`,
+    /// shell-history style: `Older` steps from "nothing recalled" to the
+    /// most recent entry and then further back through older ones; `Newer`
+    /// steps forward again, clearing the field once past the most recent
+    /// entry. A no-op with an empty history.
+    pub fn recall_size_history(&mut self, direction: HistoryDirection) {
+        if self.size_history.is_empty() {
+            return;
+        }
+        let last = self.size_history.len() - 1;
+        self.history_cursor = match (direction, self.history_cursor) {
+            (HistoryDirection::Older, None) => Some(last),
+            (HistoryDirection::Older, Some(0)) => Some(0),
+            (HistoryDirection::Older, Some(i)) => Some(i - 1),
+            (HistoryDirection::Newer, None) => None,
+            (HistoryDirection::Newer, Some(i)) if i == last => None,
+            (HistoryDirection::Newer, Some(i)) => Some(i + 1),
+        };
+        match self.history_cursor {
+            Some(i) => self.create_input.set(self.size_history[i].clone()),
+            None => self.create_input.set(String::new()),
+        }
+    }
+
+    /// Parses the text entered in the create-size prompt into a sector count
+    /// within `free`, the free region the new partition is being carved out
+    /// of. Full grammar:
+    ///
+    /// - a plain size (`10GiB`, `500MB`) — see `ByteSize` for the IEC
+    ///   convention.
+    /// - `*` or `rest` — all of `free`.
+    /// - `half` — 50% of `free`; `quarter` — 25% of `free`. Shorthand for the
+    ///   `%` form below, for the splits common enough to type by name.
+    /// - `50%` — that percentage of `free`.
+    /// - `-4GiB` or `rest-4GiB` — all of `free` minus the given size, leaving
+    ///   that much (or slightly more, rounded up to the next alignment
+    ///   boundary) trailing free space. Useful for over-provisioning or
+    ///   leaving room past the automatic GPT backup headers.
+    ///
+    /// Rejects a result smaller than `filesystem`'s
+    /// [`FileSystem::minimum_bytes`] or larger than its
+    /// [`FileSystem::maximum_bytes`], so an unusably small or unaddressably
+    /// large partition is caught here instead of failing cryptically in
+    /// `apply`.
+    pub fn handle_create(&self, text: &str, free: DiskSpace, sector_size: u64, filesystem: FileSystem) -> Result<u64> {
+        let text = text.trim();
+
+        let sectors = if text == "*" || text == "rest" {
+            free.sectors
+        } else if text == "half" {
+            percent_of_sectors(free.sectors, 50.0)
+        } else if text == "quarter" {
+            percent_of_sectors(free.sectors, 25.0)
+        } else if let Some(percent) = text.strip_suffix('%') {
+            let percent: f64 = percent
+                .parse()
+                .with_context(|| format!("'{text}' is not a valid percentage"))?;
+            anyhow::ensure!(
+                (0.0..=100.0).contains(&percent),
+                "percentage must be between 0 and 100, got {percent}"
+            );
+            percent_of_sectors(free.sectors, percent)
+        } else if let Some(rest) = text.strip_prefix("rest-").or_else(|| text.strip_prefix('-')) {
+            let size: ByteSize = rest
+                .parse()
+                .with_context(|| format!("'{text}' is not a valid size expression"))?;
+            let subtract = size.to_sectors(sector_size);
+            anyhow::ensure!(
+                subtract < free.sectors,
+                "'{text}' reserves more than the {} sectors available in this free region",
+                free.sectors
+            );
+
+            // Round the partition's end down to the nearest alignment
+            // boundary so the reserved tail also starts aligned, instead of
+            // splitting one alignment unit between the partition and the gap.
+            let align = align_sectors(sector_size);
+            let raw_end = free.start + (free.sectors - subtract);
+            let aligned_end = (raw_end / align) * align;
+            let sectors = aligned_end.saturating_sub(free.start);
+            anyhow::ensure!(
+                sectors > 0,
+                "'{text}' leaves no room for the partition once aligned to a {align}-sector boundary"
+            );
+            sectors
+        } else {
+            let size: ByteSize = text
+                .parse()
+                .with_context(|| format!("'{text}' is not a valid size expression"))?;
+            size.to_sectors(sector_size)
+        };
+
+        let minimum = filesystem.minimum_bytes();
+        anyhow::ensure!(
+            sectors * sector_size >= minimum,
+            "'{text}' is too small for {}: needs at least {}",
+            filesystem.as_ref(),
+            crate::tui::data::partition::format_size(minimum)
+        );
+        if let Some(maximum) = filesystem.maximum_bytes() {
+            anyhow::ensure!(
+                sectors * sector_size <= maximum,
+                "'{text}' is too large for {}: {} exceeds its {} addressable limit",
+                filesystem.as_ref(),
+                crate::tui::data::partition::format_size(sectors * sector_size),
+                crate::tui::data::partition::format_size(maximum)
+            );
+        }
+
+        crate::logger::log_event(
+            "partition-created",
+            &[
+                ("filesystem", filesystem.as_ref()),
+                ("start", &free.start.to_string()),
+                ("sectors", &sectors.to_string()),
+            ],
+        );
+
+        Ok(sectors)
+    }
+
+    /// Validates `text`, typed into `mountpoint_input` for "Set mountpoint",
+    /// into the path to store on the selected `MemPartition`. Must be an
+    /// absolute path, and must not already be claimed by another partition
+    /// — `existing_mountpoints` is every other partition's current
+    /// `mountpoint` on the device, so two partitions can't both claim `/`
+    /// and produce a broken `fstab`.
+    pub fn handle_set_mountpoint(&self, text: &str, existing_mountpoints: &[&str]) -> Result<String> {
+        let text = text.trim();
+        anyhow::ensure!(text.starts_with('/'), "'{text}' is not an absolute path — mountpoints must start with '/'");
+        anyhow::ensure!(
+            !existing_mountpoints.contains(&text),
+            "'{text}' is already the mountpoint for another partition"
+        );
+        Ok(text.to_string())
+    }
+
+    /// Validates `text`, typed into `label_input` for "Set label", into the
+    /// value to store on the selected `MemPartition::label`. An empty
+    /// (post-trim) label clears it — `None` — rather than being rejected,
+    /// since removing a label is a normal thing to want. Otherwise it must
+    /// fit `filesystem`'s [`FileSystem::max_label_len`].
+    pub fn handle_set_label(&self, text: &str, filesystem: FileSystem) -> Result<Option<String>> {
+        let text = text.trim();
+        if text.is_empty() {
+            return Ok(None);
+        }
+        let max = filesystem.max_label_len();
+        anyhow::ensure!(
+            text.len() <= max,
+            "'{text}' is {} character(s) long, but {} labels are limited to {max}",
+            text.len(),
+            filesystem.as_ref()
+        );
+        Ok(Some(text.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn free(sectors: u64) -> DiskSpace {
+        DiskSpace { start: 0, sectors }
+    }
+
+    #[test]
+    fn star_takes_all_free_space() {
+        let editor = DiskEditor::default();
+        // 1,000,000 sectors * 512 bytes clears ext4's minimum.
+        assert_eq!(editor.handle_create("*", free(1_000_000), 512, FileSystem::Ext4).unwrap(), 1_000_000);
+    }
+
+    #[test]
+    fn rest_is_an_alias_for_star() {
+        let editor = DiskEditor::default();
+        assert_eq!(editor.handle_create("rest", free(1_000_000), 512, FileSystem::Ext4).unwrap(), 1_000_000);
+    }
+
+    #[test]
+    fn half_and_quarter_are_shorthand_for_the_matching_percentage() {
+        let editor = DiskEditor::default();
+        let free_region = free(1_000_000);
+        assert_eq!(
+            editor.handle_create("half", free_region, 512, FileSystem::Ext4).unwrap(),
+            editor.handle_create("50%", free_region, 512, FileSystem::Ext4).unwrap(),
+        );
+        assert_eq!(
+            editor.handle_create("quarter", free_region, 512, FileSystem::Ext4).unwrap(),
+            editor.handle_create("25%", free_region, 512, FileSystem::Ext4).unwrap(),
+        );
+    }
+
+    #[test]
+    fn rest_minus_reserves_trailing_space() {
+        let editor = DiskEditor::default();
+        // 1 GiB free, minus 512 MiB -> half remains, in 512-byte sectors.
+        let free_region = free((1024u64 * 1024 * 1024) / 512);
+        let sectors = editor.handle_create("-512MiB", free_region, 512, FileSystem::Ext4).unwrap();
+        assert_eq!(sectors, (512u64 * 1024 * 1024) / 512);
+    }
+
+    #[test]
+    fn rest_minus_aligns_the_reserved_boundary() {
+        let editor = DiskEditor::default();
+        // 1 GiB free starting at an aligned sector, minus a reservation that
+        // isn't a whole number of alignment units (1 MiB = 2048 sectors at
+        // 512 bytes/sector). The partition should shrink to the next aligned
+        // boundary rather than leaving a sub-alignment sliver either side.
+        let free_region = DiskSpace { start: 2048, sectors: (1024u64 * 1024 * 1024) / 512 };
+        let sectors = editor.handle_create("-100KiB", free_region, 512, FileSystem::Ext4).unwrap();
+        let align = 1024 * 1024 / 512;
+        assert_eq!((free_region.start + sectors) % align, 0);
+        // And it shouldn't have grown past the unaligned request.
+        assert!(sectors <= free_region.sectors - (100 * 1024 / 512));
+    }
+
+    #[test]
+    fn rest_minus_rejects_amount_larger_than_free_region() {
+        let editor = DiskEditor::default();
+        assert!(editor.handle_create("-4GiB", free(100), 512, FileSystem::Ext4).is_err());
+    }
+
+    #[test]
+    fn size_to_sectors_accounts_for_a_4kn_sector_size() {
+        let editor = DiskEditor::default();
+        // A 1 GiB free region: on a 4096-byte-sector disk that size is a
+        // quarter of the sector count it takes at 512 bytes — verifying `to_sectors` (not a hardcoded 512) drives
+        // this, per synth-1701's audit of handle_create's size/sector_size math.
+        let free_region = free((1024u64 * 1024 * 1024) / 4096);
+        let sectors = editor.handle_create("1GiB", free_region, 4096, FileSystem::Ext4).unwrap();
+        assert_eq!(sectors, (1024u64 * 1024 * 1024) / 4096);
+    }
+
+    #[test]
+    fn rejects_a_partition_smaller_than_the_filesystem_minimum() {
+        let editor = DiskEditor::default();
+        // 1 MiB is nowhere near btrfs's 256 MiB floor.
+        assert!(editor.handle_create("1MiB", free(1_000_000), 512, FileSystem::Btrfs).is_err());
+    }
+
+    #[test]
+    fn rejects_a_partition_larger_than_the_filesystem_maximum() {
+        let editor = DiskEditor::default();
+        // 3 TiB clears fat32's 2 TiB practical ceiling.
+        let sectors_for_3tib = 3u64 * 1024 * 1024 * 1024 * 1024 / 512;
+        assert!(editor.handle_create("*", free(sectors_for_3tib), 512, FileSystem::Fat32).is_err());
+    }
+
+    #[test]
+    fn a_filesystem_with_no_practical_maximum_accepts_a_huge_partition() {
+        let editor = DiskEditor::default();
+        let sectors_for_3tib = 3u64 * 1024 * 1024 * 1024 * 1024 / 512;
+        assert!(editor.handle_create("*", free(sectors_for_3tib), 512, FileSystem::Btrfs).is_ok());
+    }
+
+    /// `*`, `%`, and plain-size all funnel through `f64` at some point
+    /// (`handle_create`'s percentage math casts to `f64`, and `ByteSize`
+    /// parsing does too) — verify none of that loses precision on a
+    /// synthetic 16 TiB free region, where sector counts reach ~3.4e10 (512
+    /// byte sectors) and stay far below `f64`'s 2^53 exact-integer ceiling.
+    #[test]
+    fn star_and_percent_are_exact_on_a_16tib_free_region() {
+        let editor = DiskEditor::default();
+        let sectors = 16u64 * 1024 * 1024 * 1024 * 1024 / 512;
+        let free_region = free(sectors);
+
+        assert_eq!(editor.handle_create("*", free_region, 512, FileSystem::Ext4).unwrap(), sectors);
+        assert_eq!(editor.handle_create("50%", free_region, 512, FileSystem::Ext4).unwrap(), sectors / 2);
+    }
+
+    #[test]
+    fn plain_size_is_exact_on_a_16tib_free_region() {
+        let editor = DiskEditor::default();
+        let sectors = 16u64 * 1024 * 1024 * 1024 * 1024 / 512;
+        let free_region = free(sectors);
+
+        assert_eq!(editor.handle_create("8TiB", free_region, 512, FileSystem::Ext4).unwrap(), sectors / 2);
+    }
+
+    #[test]
+    fn mkfs_options_is_none_when_the_field_is_left_empty() {
+        let editor = DiskEditor::default();
+        assert!(editor.mkfs_options().unwrap().is_none());
+    }
+
+    #[test]
+    fn mkfs_options_returns_the_trimmed_text_when_set() {
+        let mut editor = DiskEditor::default();
+        editor.mkfs_options_input.set("  -m 0  ");
+        assert_eq!(editor.mkfs_options().unwrap(), Some("-m 0".to_string()));
+    }
+
+    #[test]
+    fn mkfs_options_rejects_shell_metacharacters() {
+        let mut editor = DiskEditor::default();
+        editor.mkfs_options_input.set("-m 0; rm -rf /");
+        assert!(editor.mkfs_options().is_err());
+    }
+
+    #[test]
+    fn recall_size_history_is_a_no_op_with_nothing_submitted_yet() {
+        let mut editor = DiskEditor::default();
+        editor.recall_size_history(HistoryDirection::Older);
+        assert_eq!(editor.create_input.as_str(), "");
+    }
+
+    #[test]
+    fn older_then_newer_recalls_and_then_clears_back_to_typing() {
+        let mut editor = DiskEditor::default();
+        editor.push_size_history("10GiB");
+        editor.push_size_history("20GiB");
+
+        editor.recall_size_history(HistoryDirection::Older);
+        assert_eq!(editor.create_input.as_str(), "20GiB");
+        editor.recall_size_history(HistoryDirection::Older);
+        assert_eq!(editor.create_input.as_str(), "10GiB");
+        // Already at the oldest entry — stays put rather than wrapping.
+        editor.recall_size_history(HistoryDirection::Older);
+        assert_eq!(editor.create_input.as_str(), "10GiB");
+
+        editor.recall_size_history(HistoryDirection::Newer);
+        assert_eq!(editor.create_input.as_str(), "20GiB");
+        editor.recall_size_history(HistoryDirection::Newer);
+        assert_eq!(editor.create_input.as_str(), "");
+    }
+
+    #[test]
+    fn push_size_history_deduplicates_an_immediate_repeat() {
+        let mut editor = DiskEditor::default();
+        editor.push_size_history("10GiB");
+        editor.push_size_history("10GiB");
+
+        editor.recall_size_history(HistoryDirection::Older);
+        assert_eq!(editor.create_input.as_str(), "10GiB");
+        editor.recall_size_history(HistoryDirection::Older);
+        assert_eq!(editor.create_input.as_str(), "10GiB");
+    }
+
+    #[test]
+    fn handle_set_mountpoint_accepts_a_valid_absolute_path() {
+        let editor = DiskEditor::default();
+        assert_eq!(editor.handle_set_mountpoint("/home", &[]).unwrap(), "/home");
+    }
+
+    #[test]
+    fn handle_set_mountpoint_rejects_a_relative_path() {
+        let editor = DiskEditor::default();
+        assert!(editor.handle_set_mountpoint("home", &[]).is_err());
+    }
+
+    #[test]
+    fn handle_set_mountpoint_rejects_a_duplicate() {
+        let editor = DiskEditor::default();
+        assert!(editor.handle_set_mountpoint("/", &["/", "/boot"]).is_err());
+    }
+
+    #[test]
+    fn handle_set_label_accepts_a_label_within_the_limit() {
+        let editor = DiskEditor::default();
+        assert_eq!(editor.handle_set_label("home", FileSystem::Ext4).unwrap(), Some("home".to_string()));
+    }
+
+    #[test]
+    fn handle_set_label_clears_the_label_when_left_empty() {
+        let editor = DiskEditor::default();
+        assert_eq!(editor.handle_set_label("   ", FileSystem::Ext4).unwrap(), None);
+    }
+
+    #[test]
+    fn handle_set_label_rejects_a_label_longer_than_the_filesystem_allows() {
+        let editor = DiskEditor::default();
+        assert!(editor.handle_set_label("this-label-is-far-too-long-for-fat32", FileSystem::Fat32).is_err());
+    }
+
+    #[test]
+    fn push_size_history_caps_at_the_configured_capacity() {
+        let mut editor = DiskEditor::default();
+        for i in 0..(SIZE_HISTORY_CAPACITY + 5) {
+            editor.push_size_history(&format!("{i}GiB"));
+        }
+        assert_eq!(editor.size_history.len(), SIZE_HISTORY_CAPACITY);
+        // The oldest entries were evicted; the most recent one recalls first.
+        editor.recall_size_history(HistoryDirection::Older);
+        assert_eq!(editor.create_input.as_str(), format!("{}GiB", SIZE_HISTORY_CAPACITY + 4));
+    }
+}
@@ -0,0 +1,158 @@
+//! Read-only preview of the exact files the installer will generate,
+//! assembled from pure `generate_*` functions so what's shown here can never
+//! drift from what `apply` actually writes.
+
+use anyhow::Result;
+use crossterm::event::{Event, KeyCode, KeyEventKind};
+use ratatui::{
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+
+use crate::config::Config;
+use crate::tui::data::partition::FstabKeyMode;
+use crate::tui::views::{centered_rect, render_keybinding_hint, split_body_and_hint, Msg, View};
+use crate::tui::widgets::menu::searchable::SearchableMenu;
+use crate::tui::widgets::menu::MenuArgs;
+
+const SUMMARY_TIP: &str = "↑/↓ j/k scroll · f: fstab device keying · Esc back";
+
+/// `/etc/locale.gen` lines to uncomment for the chosen locale. Every locale
+/// currently offered has exactly one encoding (see
+/// `locale::resolve_encoding`), so this doesn't need to consult that logic
+/// yet — it always emits UTF-8.
+pub fn generate_locale_gen(locale: Option<&str>) -> String {
+    match locale {
+        Some(locale) => format!("{locale} UTF-8\n"),
+        None => "# no locale selected\n".to_string(),
+    }
+}
+
+/// `/etc/crypttab`. No disk-encryption support exists yet, so this is always
+/// the standard empty-table header.
+pub fn generate_crypttab() -> String {
+    "# <name>\t<device>\t<password>\t<options>\n".to_string()
+}
+
+/// Scrollable, read-only preview of locale.gen/fstab/crypttab as they'd be
+/// written by `apply`. This view only has `Config`, not the device list, so
+/// the fstab section notes that it isn't available yet rather than guessing.
+/// The one piece of `Config` this view does let the user change is
+/// `fstab_mode`, via `fstab_mode_editor` — everything else here is generated,
+/// not configured.
+pub struct Summary {
+    scroll: u16,
+    fstab_mode_editor: SearchableMenu,
+    show_fstab_mode_editor: bool,
+}
+
+impl Summary {
+    pub fn new(config: &Config) -> Self {
+        let items = FstabKeyMode::selectable().iter().map(|mode| mode.as_ref().to_string()).collect();
+        let mut fstab_mode_editor = SearchableMenu::new(items, MenuArgs::default().title("Fstab device keying".into()));
+        fstab_mode_editor.select(FstabKeyMode::selectable().iter().position(|mode| *mode == config.fstab_mode));
+        Self { scroll: 0, fstab_mode_editor, show_fstab_mode_editor: false }
+    }
+
+    fn text(&self, config: &Config) -> String {
+        format!(
+            "# /etc/locale.gen\n{}\n# /etc/fstab\n(partition disks first to preview fstab; device keying: {})\n\n# /etc/crypttab\n{}",
+            generate_locale_gen(config.locale.as_deref()),
+            config.fstab_mode.as_ref(),
+            generate_crypttab(),
+        )
+    }
+
+    fn open_fstab_mode_editor(&mut self, config: &Config) {
+        self.fstab_mode_editor.select(FstabKeyMode::selectable().iter().position(|mode| *mode == config.fstab_mode));
+        self.show_fstab_mode_editor = true;
+    }
+
+    /// Writes `fstab_mode_editor`'s current selection back onto `config`.
+    fn apply_fstab_mode_editor(&mut self, config: &mut Config) {
+        let Some(label) = self.fstab_mode_editor.current_item().cloned() else { return };
+        let Some(mode) = FstabKeyMode::selectable().iter().find(|mode| mode.as_ref() == label) else { return };
+        config.fstab_mode = *mode;
+    }
+}
+
+impl View for Summary {
+    fn render(&mut self, frame: &mut Frame, config: &Config) {
+        let (body, hint) = split_body_and_hint(frame.size());
+        let block = Block::default().borders(Borders::ALL).title("Generated configs (read-only)");
+        let paragraph = Paragraph::new(self.text(config))
+            .block(block)
+            .wrap(Wrap { trim: false })
+            .scroll((self.scroll, 0));
+        frame.render_widget(paragraph, body);
+        render_keybinding_hint(frame, hint, SUMMARY_TIP);
+
+        if self.show_fstab_mode_editor {
+            let area = centered_rect(50, 40, frame.size());
+            frame.render_widget(Clear, area);
+            self.fstab_mode_editor.render(frame, area);
+        }
+    }
+
+    fn on_event(&mut self, event: Event, config: &mut Config) -> Result<Option<Msg>> {
+        if let Event::Key(key) = event {
+            if key.kind != KeyEventKind::Press {
+                return Ok(None);
+            }
+
+            if self.show_fstab_mode_editor {
+                match self.fstab_mode_editor.on_event(&Event::Key(key)) {
+                    Some(true) => {
+                        self.apply_fstab_mode_editor(config);
+                        self.show_fstab_mode_editor = false;
+                    }
+                    Some(false) => self.show_fstab_mode_editor = false,
+                    None => {}
+                }
+                return Ok(None);
+            }
+
+            match key.code {
+                KeyCode::Down | KeyCode::Char('j') => self.scroll = self.scroll.saturating_add(1),
+                KeyCode::Up | KeyCode::Char('k') => self.scroll = self.scroll.saturating_sub(1),
+                KeyCode::Char('f') => self.open_fstab_mode_editor(config),
+                KeyCode::Esc => return Ok(Some(Msg::Pop)),
+                _ => {}
+            }
+        }
+        Ok(None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn locale_gen_uses_utf8_for_the_selected_locale() {
+        assert_eq!(generate_locale_gen(Some("en_US.UTF-8")), "en_US.UTF-8 UTF-8\n");
+    }
+
+    #[test]
+    fn locale_gen_notes_when_nothing_is_selected() {
+        assert_eq!(generate_locale_gen(None), "# no locale selected\n");
+    }
+
+    #[test]
+    fn crypttab_is_the_empty_header_with_no_encryption_support() {
+        assert_eq!(generate_crypttab(), "# <name>\t<device>\t<password>\t<options>\n");
+    }
+
+    #[test]
+    fn applying_the_fstab_mode_editor_writes_the_selection_back_to_config() {
+        let mut config = Config::default();
+        let mut summary = Summary::new(&config);
+
+        summary.open_fstab_mode_editor(&config);
+        let label_index = FstabKeyMode::selectable().iter().position(|mode| *mode == FstabKeyMode::PartUuid).unwrap();
+        summary.fstab_mode_editor.select(Some(label_index));
+        summary.apply_fstab_mode_editor(&mut config);
+
+        assert_eq!(config.fstab_mode, FstabKeyMode::PartUuid);
+    }
+}
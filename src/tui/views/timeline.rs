@@ -0,0 +1,63 @@
+//! Shows the full history of completed install phases (name/status/elapsed),
+//! as opposed to the progress bar shown during install, which only tracks
+//! the current step.
+
+use anyhow::Result;
+use crossterm::event::{Event, KeyCode, KeyEventKind};
+use ratatui::{
+    layout::Constraint,
+    widgets::{Block, Borders, Row, Table},
+    Frame,
+};
+
+use crate::config::Config;
+use crate::install::PhaseTimeline;
+use crate::tui::views::{render_keybinding_hint, split_body_and_hint, Msg, View};
+
+/// Keybinding hint: Esc is the only key this read-only view handles.
+const TIP: &str = "Esc back";
+
+/// Read-only view over a `PhaseTimeline` snapshot taken when the view was
+/// pushed; it doesn't itself run or watch the install.
+pub struct Timeline {
+    timeline: PhaseTimeline,
+}
+
+impl Timeline {
+    pub fn new(timeline: PhaseTimeline) -> Self {
+        Self { timeline }
+    }
+}
+
+impl View for Timeline {
+    fn render(&mut self, frame: &mut Frame, _config: &Config) {
+        let (body, hint) = split_body_and_hint(frame.size());
+        let rows: Vec<Row> = self
+            .timeline
+            .records
+            .iter()
+            .map(|record| {
+                Row::new(vec![
+                    record.name.clone(),
+                    record.status.label().to_string(),
+                    format!("{:.1}s", record.elapsed.as_secs_f64()),
+                ])
+            })
+            .collect();
+        let table = Table::new(rows)
+            .header(Row::new(vec!["Phase", "Status", "Elapsed"]))
+            .block(Block::default().borders(Borders::ALL).title("Install history"))
+            .widths(&[Constraint::Percentage(50), Constraint::Percentage(25), Constraint::Percentage(25)]);
+        frame.render_widget(table, body);
+        render_keybinding_hint(frame, hint, TIP);
+    }
+
+    fn on_event(&mut self, event: Event, _config: &mut Config) -> Result<Option<Msg>> {
+        if let Event::Key(key) = event {
+            if key.kind == KeyEventKind::Press && key.code == KeyCode::Esc {
+                return Ok(Some(Msg::Pop));
+            }
+        }
+        Ok(None)
+    }
+}
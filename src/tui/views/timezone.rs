@@ -0,0 +1,101 @@
+use anyhow::Result;
+use crossterm::event::{Event, KeyCode, KeyEventKind};
+use ratatui::Frame;
+
+use crate::config::Config;
+use crate::favorites::Favorites;
+use crate::tui::views::{is_clear_key, render_keybinding_hint, split_body_and_hint, Msg, View, FAVORITES_TIP};
+use crate::tui::widgets::menu::{Menu, MenuArgs};
+
+/// Key `Favorites` entries for this menu are filed under.
+const MENU_NAME: &str = "timezone";
+
+/// Hard-coded for now; a real implementation would enumerate the tzdata zone
+/// list.
+fn timezones() -> Vec<String> {
+    vec![
+        "UTC".into(),
+        "America/New_York".into(),
+        "Europe/Berlin".into(),
+        "Asia/Tokyo".into(),
+    ]
+}
+
+pub struct Timezone {
+    menu: Menu,
+    favorites: Favorites,
+}
+
+impl Timezone {
+    pub fn new(config: &Config) -> Self {
+        let favorites = Favorites::load();
+        let items = favorites.pin(MENU_NAME, timezones());
+        let mut menu = Menu::new(items.clone(), MenuArgs::default().title("Timezone".into()));
+        if let Some(current) = &config.timezone {
+            if let Some(index) = items.iter().position(|item| item == current) {
+                menu.select(Some(index));
+            }
+        }
+        Self { menu, favorites }
+    }
+
+    /// Stars/un-stars the selected timezone and re-pins the list so the
+    /// change is reflected immediately.
+    fn toggle_favorite(&mut self) {
+        let Some(current) = self.menu.current_item().cloned() else {
+            return;
+        };
+        self.favorites.toggle(MENU_NAME, &current);
+        let _ = self.favorites.save();
+        let items = self.favorites.pin(MENU_NAME, timezones());
+        self.menu.update_items(items.clone());
+        self.menu.select(items.iter().position(|item| *item == current));
+    }
+}
+
+impl View for Timezone {
+    fn render(&mut self, frame: &mut Frame, _config: &Config) {
+        let (body, hint) = split_body_and_hint(frame.size());
+        self.menu.render(frame, body);
+        render_keybinding_hint(frame, hint, FAVORITES_TIP);
+    }
+
+    fn on_event(&mut self, event: Event, config: &mut Config) -> Result<Option<Msg>> {
+        if is_clear_key(&event) {
+            config.timezone = None;
+            self.menu.select(None);
+            return Ok(None);
+        }
+
+        if let Event::Mouse(mouse) = &event {
+            self.menu.handle_mouse(mouse);
+            return Ok(None);
+        }
+
+        if let Event::Key(key) = event {
+            if key.kind != KeyEventKind::Press {
+                return Ok(None);
+            }
+            match key.code {
+                KeyCode::Down | KeyCode::Char('j') => self.menu.selectable.select_next_item(),
+                KeyCode::Up | KeyCode::Char('k') => self.menu.selectable.select_prev_item(),
+                KeyCode::Char('*') => self.toggle_favorite(),
+                KeyCode::Enter => {
+                    if let Some(tz) = self.menu.current_item() {
+                        config.timezone = Some(tz.clone());
+                    }
+                    return Ok(Some(Msg::Pop));
+                }
+                KeyCode::Esc => return Ok(Some(Msg::Pop)),
+                // Type-ahead jump, e.g. pressing 'a' jumps to "America/...".
+                // Any command letter above (j, k, *) is matched first, so it
+                // never reaches here.
+                KeyCode::Char(c) => {
+                    self.menu.jump_to_prefix(c);
+                }
+                _ => {}
+            }
+        }
+        Ok(None)
+    }
+}
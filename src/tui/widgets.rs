@@ -0,0 +1,6 @@
+pub mod confirm;
+pub mod focus_ring;
+pub mod input;
+pub mod menu;
+pub mod selectable;
+pub mod toggle_list;
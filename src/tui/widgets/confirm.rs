@@ -0,0 +1,104 @@
+//! A reusable yes/no confirmation popup — replacing the hand-rolled
+//! `show_*_confirm: bool` + "match `key.code == 'y'`" pattern that would
+//! otherwise get re-invented at every new call site.
+
+use crossterm::event::{Event, KeyCode, KeyEventKind};
+use ratatui::{
+    layout::Rect,
+    text::Line,
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use crate::macros::widget_args;
+use crate::tui::views::centered_rect;
+
+widget_args! {
+    pub struct ConfirmArgs {
+        /// Shown as the popup's border title, e.g. "Quit? y/n".
+        message: String = String::new(),
+        /// What plain Enter (rather than an explicit y/n) picks.
+        default: bool = false,
+    }
+}
+
+pub struct Confirm {
+    args: ConfirmArgs,
+}
+
+impl Confirm {
+    pub fn new(args: ConfirmArgs) -> Self {
+        Self { args }
+    }
+
+    /// Maps a key event to a decision: `y` confirms, `n` and Esc decline,
+    /// Enter picks `args.default`, and everything else is ignored so a
+    /// stray keystroke doesn't accidentally confirm or dismiss the popup.
+    pub fn on_event(&self, event: &Event) -> Option<bool> {
+        let Event::Key(key) = event else {
+            return None;
+        };
+        if key.kind != KeyEventKind::Press {
+            return None;
+        }
+
+        match key.code {
+            KeyCode::Char('y') => Some(true),
+            KeyCode::Char('n') => Some(false),
+            KeyCode::Enter => Some(self.args.default),
+            KeyCode::Esc => Some(false),
+            _ => None,
+        }
+    }
+
+    pub fn render(&self, frame: &mut Frame, area: Rect) {
+        let area = centered_rect(50, 3, area);
+        let block = Block::default().borders(Borders::ALL).title(self.args.message.clone());
+        frame.render_widget(Clear, area);
+        frame.render_widget(Paragraph::new(Line::from("y to confirm, n or Esc to cancel")).block(block), area);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::event::{KeyEvent, KeyModifiers};
+
+    use super::*;
+
+    fn key(code: KeyCode) -> Event {
+        Event::Key(KeyEvent::new(code, KeyModifiers::NONE))
+    }
+
+    #[test]
+    fn y_confirms_regardless_of_default() {
+        let confirm = Confirm::new(ConfirmArgs::default().default(false));
+        assert_eq!(confirm.on_event(&key(KeyCode::Char('y'))), Some(true));
+    }
+
+    #[test]
+    fn n_declines_regardless_of_default() {
+        let confirm = Confirm::new(ConfirmArgs::default().default(true));
+        assert_eq!(confirm.on_event(&key(KeyCode::Char('n'))), Some(false));
+    }
+
+    #[test]
+    fn escape_always_declines() {
+        let confirm = Confirm::new(ConfirmArgs::default().default(true));
+        assert_eq!(confirm.on_event(&key(KeyCode::Esc)), Some(false));
+    }
+
+    #[test]
+    fn enter_picks_the_configured_default() {
+        let confirming = Confirm::new(ConfirmArgs::default().default(true));
+        assert_eq!(confirming.on_event(&key(KeyCode::Enter)), Some(true));
+
+        let declining = Confirm::new(ConfirmArgs::default().default(false));
+        assert_eq!(declining.on_event(&key(KeyCode::Enter)), Some(false));
+    }
+
+    #[test]
+    fn an_unrelated_key_is_ignored() {
+        let confirm = Confirm::new(ConfirmArgs::default());
+        assert_eq!(confirm.on_event(&key(KeyCode::Char('x'))), None);
+    }
+}
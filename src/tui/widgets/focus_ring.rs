@@ -0,0 +1,17 @@
+//! Shared border-color convention for multi-focus views (`MasterDetail`'s
+//! two panes, and any future split), so which pane has keyboard focus looks
+//! the same everywhere instead of each view inventing its own treatment.
+
+use ratatui::style::{Color, Style};
+
+/// The color a pane's border takes on while it has keyboard focus.
+pub const FOCUS_COLOR: Color = Color::Cyan;
+
+/// Border `Style` for a pane, given whether it currently has focus.
+pub fn border_style(focused: bool) -> Style {
+    if focused {
+        Style::default().fg(FOCUS_COLOR)
+    } else {
+        Style::default()
+    }
+}
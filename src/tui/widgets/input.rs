@@ -0,0 +1,551 @@
+//! A single-line text input widget used by every popup that collects free
+//! text (hostname, partition size, mountpoint, ...).
+
+use crossterm::event::{Event, KeyCode, KeyEventKind, KeyModifiers};
+use ratatui::{
+    layout::Rect,
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph},
+    Frame,
+};
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+use crate::extensions::StrExt;
+use crate::macros::widget_args;
+
+/// What `Input::on_event` should do when Enter is pressed on an empty field.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum EmptyEnter {
+    /// Signal `InputCommand::Empty` — every current caller closes the popup
+    /// exactly like a real Escape (today's default, kept for existing
+    /// callers), but the two are now distinguishable so a future one can
+    /// react differently, e.g. show "hostname can't be empty" instead of
+    /// silently discarding the popup.
+    #[default]
+    Cancel,
+    /// Do nothing; the popup stays open waiting for real input.
+    Ignore,
+    /// Submit the empty string, e.g. so "Submit empty" can mean "clear this
+    /// field" for the mountpoint/label editors.
+    SubmitEmpty,
+}
+
+widget_args! {
+    pub struct InputArgs {
+        title: String = String::new(),
+        on_empty_enter: EmptyEnter = EmptyEnter::default(),
+        /// Dimmed hint shown while the field is empty, e.g. "optional" on the
+        /// mkfs-options prompt. Empty string means no placeholder.
+        placeholder: String = String::new(),
+        /// Whether Up/Down cycle through previously submitted values,
+        /// shell-history style. Off by default — a plain one-shot field (a
+        /// hostname, say) shouldn't have its arrow keys silently repurposed.
+        with_history: bool = false,
+    }
+}
+
+/// Which way `Input::recall_history` steps through `history` — named after
+/// the direction in time rather than Up/Down, since the arrow-key mapping
+/// lives in `on_event`, not here.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum HistoryDirection {
+    /// Toward earlier entries — Up.
+    Older,
+    /// Back toward the present, and eventually the user's own uncommitted
+    /// draft — Down.
+    Newer,
+}
+
+/// How many previously-submitted values `Input::history` keeps. Matches
+/// `DiskEditor::size_history`'s cap for consistency.
+const HISTORY_CAPACITY: usize = 20;
+
+/// Result of feeding an event to `Input::on_event`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InputCommand {
+    /// Enter was pressed with non-empty content.
+    Submit,
+    /// Esc was pressed.
+    Cancel,
+    /// Enter was pressed on an empty field with `EmptyEnter::Cancel` set.
+    /// Distinct from `Cancel` so a caller can tell "the user pressed Escape"
+    /// apart from "the user pressed Enter on nothing" even though both
+    /// currently close the popup the same way.
+    Empty,
+}
+
+#[derive(Debug, Default)]
+pub struct Input {
+    input: String,
+    args: InputArgs,
+    /// When set, `render` shows this char repeated once per character of
+    /// `input` instead of the real content — for password/passphrase fields.
+    /// `as_str`/`take` are unaffected: masking is display-only.
+    mask: Option<char>,
+    /// Previously-submitted values, oldest first. Only populated when
+    /// `args.with_history` is set.
+    history: Vec<String>,
+    /// Position while cycling `history`. `None` means the field is showing
+    /// the user's own typing (or a recalled entry's edits) rather than a
+    /// step through history.
+    history_cursor: Option<usize>,
+    /// The user's in-progress text, stashed the moment history recall
+    /// starts so stepping back `Newer` past the most recent entry restores
+    /// it instead of leaving the field on the last-recalled value.
+    draft: Option<String>,
+}
+
+impl Input {
+    pub fn new(args: InputArgs) -> Self {
+        Self {
+            input: String::new(),
+            args,
+            mask: None,
+            history: Vec::new(),
+            history_cursor: None,
+            draft: None,
+        }
+    }
+
+    /// An `Input` that renders as `mask` repeated per character instead of
+    /// echoing what's typed, for the root/user password screens.
+    pub fn masked(args: InputArgs, mask: char) -> Self {
+        Self { mask: Some(mask), ..Self::new(args) }
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.input
+    }
+
+    /// Consumes and returns the current contents, leaving the input empty.
+    pub fn take(&mut self) -> String {
+        std::mem::take(&mut self.input)
+    }
+
+    /// Replaces the current contents outright, e.g. to pre-fill a prompt with
+    /// a value copied from elsewhere ("duplicate this partition's size").
+    pub fn set(&mut self, text: impl Into<String>) {
+        self.input = text.into();
+    }
+
+    /// Appends `s` to the field, stripping embedded newlines first since
+    /// `Input` is single-line. Backs both bracketed-paste handling (a
+    /// terminal paste arrives as one `Event::Paste`) and lets tests drive a
+    /// paste without constructing a real paste event.
+    pub fn insert_str(&mut self, s: &str) {
+        self.input.extend(s.chars().filter(|c| *c != '\n' && *c != '\r'));
+    }
+
+    /// Records a just-submitted, non-empty value into `history`,
+    /// deduplicating an immediate repeat so mashing Enter on the same value
+    /// doesn't spam the recall list. Resets the recall cursor and draft — a
+    /// fresh submission always starts a new browsing session.
+    fn push_history(&mut self, text: &str) {
+        if text.is_empty() {
+            return;
+        }
+        if self.history.last().map(String::as_str) != Some(text) {
+            self.history.push(text.to_string());
+            if self.history.len() > HISTORY_CAPACITY {
+                self.history.remove(0);
+            }
+        }
+        self.history_cursor = None;
+        self.draft = None;
+    }
+
+    /// Cycles through `history`, shell-history style: `Older` steps from
+    /// "nothing recalled" (stashing the current draft first) to the most
+    /// recent entry and then further back through older ones; `Newer` steps
+    /// forward again, restoring the stashed draft once past the most recent
+    /// entry. A no-op with an empty history.
+    fn recall_history(&mut self, direction: HistoryDirection) {
+        if self.history.is_empty() {
+            return;
+        }
+        if self.history_cursor.is_none() {
+            self.draft = Some(self.input.clone());
+        }
+        let last = self.history.len() - 1;
+        self.history_cursor = match (direction, self.history_cursor) {
+            (HistoryDirection::Older, None) => Some(last),
+            (HistoryDirection::Older, Some(0)) => Some(0),
+            (HistoryDirection::Older, Some(i)) => Some(i - 1),
+            (HistoryDirection::Newer, None) => None,
+            (HistoryDirection::Newer, Some(i)) if i == last => None,
+            (HistoryDirection::Newer, Some(i)) => Some(i + 1),
+        };
+        self.input = match self.history_cursor {
+            Some(i) => self.history[i].clone(),
+            None => self.draft.take().unwrap_or_default(),
+        };
+    }
+
+    pub fn on_event(&mut self, event: &Event) -> Option<InputCommand> {
+        if let Event::Paste(text) = event {
+            self.insert_str(text);
+            return None;
+        }
+        let Event::Key(key) = event else {
+            return None;
+        };
+        if key.kind != KeyEventKind::Press {
+            return None;
+        }
+
+        match key.code {
+            KeyCode::Char('w') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.delete_word_before_cursor();
+                None
+            }
+            KeyCode::Char('u') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                // "Before the cursor" is the whole field: `Input` has no
+                // interior cursor movement, so the cursor is always at the
+                // end (see `cursor`).
+                self.input.clear();
+                None
+            }
+            KeyCode::Char(c) => {
+                self.input.push(c);
+                None
+            }
+            KeyCode::Up if self.args.with_history => {
+                self.recall_history(HistoryDirection::Older);
+                None
+            }
+            KeyCode::Down if self.args.with_history => {
+                self.recall_history(HistoryDirection::Newer);
+                None
+            }
+            KeyCode::Backspace => {
+                // Backspace on an already-empty input is a no-op, not a
+                // cancel: a user who clears a field by backspacing and then
+                // hits Backspace once more shouldn't unexpectedly close the
+                // whole popup. Esc is the only way to cancel.
+                self.input.pop();
+                None
+            }
+            KeyCode::Enter => {
+                if self.input.is_empty() {
+                    match self.args.on_empty_enter {
+                        EmptyEnter::Cancel => Some(InputCommand::Empty),
+                        EmptyEnter::Ignore => None,
+                        EmptyEnter::SubmitEmpty => Some(InputCommand::Submit),
+                    }
+                } else {
+                    if self.args.with_history {
+                        self.push_history(&self.input.clone());
+                    }
+                    Some(InputCommand::Submit)
+                }
+            }
+            KeyCode::Esc => Some(InputCommand::Cancel),
+            _ => None,
+        }
+    }
+
+    /// What `render` actually draws: the real content, or `mask` repeated
+    /// once per character when this is a password field. A pure function of
+    /// already-held state so masking can be tested without a real terminal.
+    fn displayed(&self) -> String {
+        match self.mask {
+            Some(mask) => mask.to_string().repeat(self.input.chars().count()),
+            None => self.input.clone(),
+        }
+    }
+
+    /// Removes the run of non-whitespace immediately before the cursor, plus
+    /// any whitespace that separated it from what comes after — i.e. Ctrl+W.
+    /// Since `Input` has no interior cursor movement, "before the cursor" is
+    /// always the trailing edge of the field.
+    fn delete_word_before_cursor(&mut self) {
+        let mut chars: Vec<char> = self.input.chars().collect();
+        while matches!(chars.last(), Some(c) if c.is_whitespace()) {
+            chars.pop();
+        }
+        while matches!(chars.last(), Some(c) if !c.is_whitespace()) {
+            chars.pop();
+        }
+        self.input = chars.into_iter().collect();
+    }
+
+    /// Whether `render` should draw the dimmed placeholder instead of the
+    /// real content: only while the field is genuinely empty and a
+    /// placeholder was configured.
+    fn shows_placeholder(&self) -> bool {
+        self.input.is_empty() && !self.args.placeholder.is_empty()
+    }
+
+    /// Character index of the edit point. `Input` only ever types/deletes at
+    /// the end of the field (there's no left/right cursor movement yet), so
+    /// this is always the field's full character length — but factored out
+    /// so `visible_window`'s scroll math reads the same way it would once
+    /// interior cursor movement exists.
+    fn cursor(&self) -> usize {
+        self.input.chars().count()
+    }
+
+    /// Slices `text` down to whatever fits in `width` display columns while
+    /// keeping `cursor` in view: the whole string if it already fits, or
+    /// otherwise the trailing window ending at `cursor` (scrolling left as
+    /// more is typed past the right edge). Pure and char/width-index safe
+    /// (via `StrExt::slice`/`UnicodeWidthChar`), so it's testable without a
+    /// real terminal and correct on multi-byte/double-width input.
+    fn visible_window(text: &str, cursor: usize, width: usize) -> &str {
+        if width == 0 || text.width() <= width {
+            return text;
+        }
+        let chars: Vec<char> = text.chars().collect();
+        let mut start = cursor;
+        let mut used = 0usize;
+        while start > 0 {
+            let char_width = UnicodeWidthChar::width(chars[start - 1]).unwrap_or(0);
+            if used + char_width > width {
+                break;
+            }
+            used += char_width;
+            start -= 1;
+        }
+        text.slice(start, cursor)
+    }
+
+    pub fn render(&self, frame: &mut Frame, area: Rect) {
+        let block = Block::default().borders(Borders::ALL).title(self.args.title.clone());
+        let inner_width = area.width.saturating_sub(2) as usize;
+        let line = if self.shows_placeholder() {
+            let visible = Self::visible_window(&self.args.placeholder, self.args.placeholder.chars().count(), inner_width);
+            Line::from(Span::styled(visible.to_string(), Style::default().fg(Color::DarkGray)))
+        } else {
+            let displayed = self.displayed();
+            let visible = Self::visible_window(&displayed, self.cursor(), inner_width);
+            Line::from(visible)
+        };
+        let paragraph = Paragraph::new(line).block(block);
+        frame.render_widget(paragraph, area);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::event::{KeyEvent, KeyModifiers};
+
+    use super::*;
+
+    fn key(code: KeyCode) -> Event {
+        Event::Key(KeyEvent::new(code, KeyModifiers::NONE))
+    }
+
+    fn ctrl_key(code: KeyCode) -> Event {
+        Event::Key(KeyEvent::new(code, KeyModifiers::CONTROL))
+    }
+
+    #[test]
+    fn backspace_on_empty_input_is_a_no_op() {
+        let mut input = Input::new(InputArgs::default());
+        assert_eq!(input.on_event(&key(KeyCode::Backspace)), None);
+        assert_eq!(input.as_str(), "");
+    }
+
+    #[test]
+    fn backspace_on_non_empty_input_deletes_a_char() {
+        let mut input = Input::new(InputArgs::default());
+        input.on_event(&key(KeyCode::Char('a')));
+        assert_eq!(input.on_event(&key(KeyCode::Backspace)), None);
+        assert_eq!(input.as_str(), "");
+    }
+
+    #[test]
+    fn empty_enter_cancel_mode_is_the_default() {
+        let mut input = Input::new(InputArgs::default());
+        assert_eq!(input.on_event(&key(KeyCode::Enter)), Some(InputCommand::Empty));
+    }
+
+    #[test]
+    fn empty_enter_is_distinct_from_a_real_escape() {
+        let mut input = Input::new(InputArgs::default());
+        assert_eq!(input.on_event(&key(KeyCode::Enter)), Some(InputCommand::Empty));
+        assert_eq!(input.on_event(&key(KeyCode::Esc)), Some(InputCommand::Cancel));
+    }
+
+    #[test]
+    fn empty_enter_ignore_mode_stays_open() {
+        let mut input = Input::new(InputArgs::default().on_empty_enter(EmptyEnter::Ignore));
+        assert_eq!(input.on_event(&key(KeyCode::Enter)), None);
+    }
+
+    #[test]
+    fn empty_enter_submit_mode_submits_empty_string() {
+        let mut input = Input::new(InputArgs::default().on_empty_enter(EmptyEnter::SubmitEmpty));
+        assert_eq!(input.on_event(&key(KeyCode::Enter)), Some(InputCommand::Submit));
+        assert_eq!(input.take(), "");
+    }
+
+    #[test]
+    fn pasting_strips_embedded_newlines() {
+        let mut input = Input::new(InputArgs::default());
+        assert_eq!(input.on_event(&Event::Paste("ab\ncd".to_string())), None);
+        assert_eq!(input.as_str(), "abcd");
+    }
+
+    #[test]
+    fn insert_str_appends_to_existing_content() {
+        let mut input = Input::new(InputArgs::default());
+        input.set("foo");
+        input.insert_str("bar");
+        assert_eq!(input.as_str(), "foobar");
+    }
+
+    #[test]
+    fn masked_input_displays_only_the_mask_char_but_as_str_returns_the_secret() {
+        let mut input = Input::masked(InputArgs::default(), '*');
+        input.insert_str("hunter2");
+        assert_eq!(input.displayed(), "*******");
+        assert_eq!(input.as_str(), "hunter2");
+        assert_eq!(input.take(), "hunter2");
+    }
+
+    #[test]
+    fn unmasked_input_displays_the_real_content() {
+        let mut input = Input::new(InputArgs::default());
+        input.insert_str("hunter2");
+        assert_eq!(input.displayed(), "hunter2");
+    }
+
+    #[test]
+    fn visible_window_fits_the_whole_string_when_it_is_shorter_than_the_width() {
+        assert_eq!(Input::visible_window("hello", 5, 10), "hello");
+    }
+
+    #[test]
+    fn visible_window_always_contains_the_cursor_column_in_a_long_string() {
+        let text: String = ('a'..='z').chain('0'..='3').collect();
+        assert_eq!(text.chars().count(), 30);
+        let cursor = text.chars().count();
+        let window = Input::visible_window(&text, cursor, 10);
+        assert!(window.width() <= 10);
+        assert!(text.ends_with(window));
+    }
+
+    #[test]
+    fn placeholder_shows_only_while_the_field_is_empty() {
+        let mut input = Input::new(InputArgs::default().placeholder("example.com".to_string()));
+        assert!(input.shows_placeholder());
+        input.on_event(&key(KeyCode::Char('a')));
+        assert!(!input.shows_placeholder());
+    }
+
+    #[test]
+    fn no_placeholder_configured_never_shows_one() {
+        let input = Input::new(InputArgs::default());
+        assert!(!input.shows_placeholder());
+    }
+
+    #[test]
+    fn placeholder_is_never_returned_by_take() {
+        let mut input = Input::new(InputArgs::default().placeholder("example.com".to_string()));
+        assert_eq!(input.take(), "");
+    }
+
+    #[test]
+    fn ctrl_w_deletes_the_trailing_word() {
+        let mut input = Input::new(InputArgs::default());
+        input.set("foo bar");
+        assert_eq!(input.on_event(&ctrl_key(KeyCode::Char('w'))), None);
+        assert_eq!(input.as_str(), "foo ");
+    }
+
+    #[test]
+    fn ctrl_w_skips_multiple_leading_spaces_before_the_word() {
+        let mut input = Input::new(InputArgs::default());
+        input.set("foo   bar");
+        assert_eq!(input.on_event(&ctrl_key(KeyCode::Char('w'))), None);
+        assert_eq!(input.as_str(), "foo   ");
+    }
+
+    #[test]
+    fn ctrl_u_clears_everything_before_the_cursor() {
+        // `Input` has no interior cursor movement yet, so "before the
+        // cursor" is always the whole field.
+        let mut input = Input::new(InputArgs::default());
+        input.set("hello world");
+        assert_eq!(input.on_event(&ctrl_key(KeyCode::Char('u'))), None);
+        assert_eq!(input.as_str(), "");
+    }
+
+    #[test]
+    fn up_recalls_the_previously_submitted_value() {
+        let mut input = Input::new(InputArgs::default().with_history(true));
+        input.set("10GiB");
+        input.on_event(&key(KeyCode::Enter));
+        input.set("20GiB");
+        input.on_event(&key(KeyCode::Enter));
+        assert_eq!(input.on_event(&key(KeyCode::Up)), None);
+        assert_eq!(input.as_str(), "20GiB");
+    }
+
+    #[test]
+    fn down_past_the_newest_entry_restores_the_in_progress_draft() {
+        let mut input = Input::new(InputArgs::default().with_history(true));
+        input.set("10GiB");
+        input.on_event(&key(KeyCode::Enter));
+        input.set("half-typed");
+        input.on_event(&key(KeyCode::Up));
+        assert_eq!(input.as_str(), "10GiB");
+        input.on_event(&key(KeyCode::Down));
+        assert_eq!(input.as_str(), "half-typed");
+    }
+
+    #[test]
+    fn cycling_older_stops_at_the_oldest_entry() {
+        let mut input = Input::new(InputArgs::default().with_history(true));
+        for value in ["10GiB", "20GiB", "30GiB"] {
+            input.set(value);
+            input.on_event(&key(KeyCode::Enter));
+        }
+        input.on_event(&key(KeyCode::Up));
+        input.on_event(&key(KeyCode::Up));
+        input.on_event(&key(KeyCode::Up));
+        input.on_event(&key(KeyCode::Up));
+        assert_eq!(input.as_str(), "10GiB");
+    }
+
+    #[test]
+    fn submitting_empty_input_does_not_pollute_history() {
+        let mut input = Input::new(InputArgs::default().with_history(true).on_empty_enter(EmptyEnter::SubmitEmpty));
+        input.on_event(&key(KeyCode::Enter));
+        input.set("10GiB");
+        input.on_event(&key(KeyCode::Enter));
+        input.set("");
+        input.on_event(&key(KeyCode::Up));
+        assert_eq!(input.as_str(), "10GiB");
+    }
+
+    #[test]
+    fn history_is_disabled_by_default_so_up_down_are_ignored() {
+        let mut input = Input::new(InputArgs::default());
+        input.set("10GiB");
+        input.on_event(&key(KeyCode::Enter));
+        assert_eq!(input.on_event(&key(KeyCode::Up)), None);
+        assert_eq!(input.as_str(), "10GiB");
+    }
+
+    #[test]
+    fn escape_still_cancels() {
+        let mut input = Input::new(InputArgs::default());
+        input.on_event(&key(KeyCode::Char('a')));
+        assert_eq!(input.on_event(&key(KeyCode::Esc)), Some(InputCommand::Cancel));
+    }
+
+    /// Letters that double as navigation/quit bindings elsewhere in the app
+    /// (`q` to quit, `j`/`k`/`g`/`G` to move) must still be typeable while an
+    /// `Input` has focus — see the focus-dispatch contract on `View`.
+    #[test]
+    fn command_like_letters_are_inserted_as_text() {
+        let mut input = Input::new(InputArgs::default());
+        for c in ['q', 'j', 'k', 'g', 'G'] {
+            assert_eq!(input.on_event(&key(KeyCode::Char(c))), None);
+        }
+        assert_eq!(input.as_str(), "qjkgG");
+    }
+}
@@ -0,0 +1,227 @@
+//! A single-select list widget rendered as a bordered `List`.
+
+pub mod searchable;
+
+use crossterm::event::MouseEvent;
+use ratatui::{
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::Line,
+    widgets::{Block, Borders, List, ListItem, Scrollbar, ScrollbarOrientation, ScrollbarState},
+    Frame,
+};
+
+use crate::macros::widget_args;
+use crate::tui::widgets::selectable::SelectableWidget;
+
+widget_args! {
+    pub struct MenuArgs {
+        title: String = String::new(),
+        /// Whether a vertical scrollbar is drawn when the item count
+        /// overflows the render area. On by default — with hundreds of
+        /// timezones/locales, scroll position would otherwise be invisible.
+        scrollbar: bool = true,
+        /// Enables `SearchableMenu`'s checkbox-style multi-select (`Space` to
+        /// toggle) — for pickers like mirror/package selection where more
+        /// than one item can be chosen. Ignored by plain `Menu`.
+        multi_select: bool = false,
+        /// Whether `select_next_item`/`select_prev_item` wrap past the ends
+        /// of the list. On by default; set to `false` for long lists where
+        /// wrapping from the last item back to the first is disorienting.
+        wrap: bool = true,
+    }
+}
+
+/// A plain (non-searchable) single-select menu. `SearchableMenu` wraps this
+/// with fuzzy filtering for larger item lists.
+#[derive(Debug, Default)]
+pub struct Menu {
+    pub selectable: SelectableWidget<String>,
+    args: MenuArgs,
+    /// The `Rect` this menu was last rendered into, so `handle_mouse` can
+    /// hit-test a click without the caller having to hand the area back in.
+    /// `None` until the first `render`/`render_with_focus` call.
+    last_area: Option<Rect>,
+}
+
+impl Menu {
+    pub fn new(items: Vec<String>, args: MenuArgs) -> Self {
+        let mut selectable = SelectableWidget::new(items);
+        selectable.set_wrap(args.wrap);
+        Self { selectable, args, last_area: None }
+    }
+
+    pub fn current_index(&self) -> Option<usize> {
+        self.selectable.current_index()
+    }
+
+    pub fn current_item(&self) -> Option<&String> {
+        self.selectable.current_item()
+    }
+
+    pub fn select(&mut self, index: Option<usize>) {
+        self.selectable.select(index);
+    }
+
+    pub fn update_items(&mut self, items: Vec<String>) {
+        self.selectable.update_state(items);
+    }
+
+    /// Type-ahead jump to the next item starting with `ch`. Strips a leading
+    /// `"[x] "`/`"[ ] "` checkbox marker (as `Main`'s entries have) before
+    /// matching, so the jump lines up with the visible label rather than the
+    /// marker.
+    pub fn jump_to_prefix(&mut self, ch: char) -> bool {
+        self.selectable.jump_to_prefix(ch, |item| {
+            item.strip_prefix("[x] ").or_else(|| item.strip_prefix("[ ] ")).unwrap_or(item)
+        })
+    }
+
+    pub fn set_title(&mut self, title: impl Into<String>) {
+        self.args.title = title.into();
+    }
+
+    /// Feeds a mouse event through to `selectable`, hit-testing against the
+    /// area this menu was last rendered into. A no-op (returns `false`)
+    /// before the first render.
+    pub fn handle_mouse(&mut self, event: &MouseEvent) -> bool {
+        match self.last_area {
+            Some(area) => self.selectable.handle_mouse(area, event),
+            None => false,
+        }
+    }
+
+    pub fn render(&mut self, frame: &mut Frame, area: Rect) {
+        self.last_area = Some(area);
+        Self::render_from_iter(
+            frame,
+            area,
+            &self.args.title,
+            self.selectable.items.iter().map(String::as_str),
+            &mut self.selectable,
+            Style::default(),
+            self.args.scrollbar,
+        );
+    }
+
+    /// Like `render`, but with `focus_ring::border_style(focused)` applied to
+    /// the border — for a menu that's one pane of a multi-focus view (e.g.
+    /// `MasterDetail`), so which pane has keyboard focus is never ambiguous.
+    pub fn render_with_focus(&mut self, frame: &mut Frame, area: Rect, focused: bool) {
+        self.last_area = Some(area);
+        Self::render_from_iter(
+            frame,
+            area,
+            &self.args.title,
+            self.selectable.items.iter().map(String::as_str),
+            &mut self.selectable,
+            crate::tui::widgets::focus_ring::border_style(focused),
+            self.args.scrollbar,
+        );
+    }
+
+    /// Shared rendering path so `SearchableMenu` can
+    /// draw a filtered subset through the same `List` styling without
+    /// duplicating the `Block`/highlight setup.
+    ///
+    /// Draws a vertical scrollbar tracking `selectable.current_index()` when
+    /// `scrollbar` is set and the item count overflows `area`; suppressed
+    /// automatically once everything fits, so a short list never grows an
+    /// idle scrollbar.
+    pub fn render_from_iter<'a>(
+        frame: &mut Frame,
+        area: Rect,
+        title: &str,
+        items: impl Iterator<Item = &'a str>,
+        selectable: &mut SelectableWidget<String>,
+        border_style: Style,
+        scrollbar: bool,
+    ) {
+        let list_items: Vec<ListItem> = items.map(|s| ListItem::new(Line::from(s))).collect();
+        let item_count = list_items.len();
+        let block = Block::default().borders(Borders::ALL).border_style(border_style).title(title.to_string());
+        let list = List::new(list_items)
+            .block(block)
+            .highlight_style(Style::default().add_modifier(Modifier::BOLD).fg(Color::Cyan))
+            .highlight_symbol("> ");
+        frame.render_stateful_widget(list, area, selectable.list_state());
+
+        if let Some(position) = Self::scrollbar_position(scrollbar, item_count, area.height, selectable.current_index()) {
+            let mut scrollbar_state = ScrollbarState::new(item_count).position(position);
+            frame.render_stateful_widget(
+                Scrollbar::new(ScrollbarOrientation::VerticalRight),
+                area,
+                &mut scrollbar_state,
+            );
+        }
+    }
+
+    /// Pure decision behind the scrollbar in `render_from_iter`: `None`
+    /// suppresses it (disabled, or `item_count` already fits `area_height`);
+    /// otherwise the position to report, always `current_index` so the
+    /// thumb tracks the highlighted item exactly.
+    fn scrollbar_position(scrollbar_enabled: bool, item_count: usize, area_height: u16, current_index: Option<usize>) -> Option<usize> {
+        let visible_rows = area_height.saturating_sub(2) as usize;
+        if scrollbar_enabled && item_count > visible_rows {
+            Some(current_index.unwrap_or(0))
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::event::{KeyModifiers, MouseButton, MouseEventKind};
+
+    use super::*;
+
+    #[test]
+    fn handle_mouse_is_a_no_op_before_the_first_render() {
+        let mut menu = Menu::new(vec!["a".into(), "b".into()], MenuArgs::default());
+        let clicked = menu.handle_mouse(&MouseEvent {
+            kind: MouseEventKind::Down(MouseButton::Left),
+            column: 1,
+            row: 1,
+            modifiers: KeyModifiers::NONE,
+        });
+        assert!(!clicked);
+        assert_eq!(menu.current_index(), None);
+    }
+
+    #[test]
+    fn clicking_a_row_after_render_selects_it() {
+        let mut menu = Menu::new(vec!["a".into(), "b".into(), "c".into()], MenuArgs::default());
+        // Same effect as a real `render` call, without needing a `Frame` to
+        // drive it: stash the area `handle_mouse` hit-tests against.
+        menu.last_area = Some(Rect { x: 0, y: 0, width: 20, height: 6 });
+
+        let clicked = menu.handle_mouse(&MouseEvent {
+            kind: MouseEventKind::Down(MouseButton::Left),
+            column: 2,
+            row: 2, // border (row 0) + item "a" (row 1) + item "b" (row 2)
+            modifiers: KeyModifiers::NONE,
+        });
+        assert!(clicked);
+        assert_eq!(menu.current_index(), Some(1));
+    }
+
+    #[test]
+    fn scrollbar_is_suppressed_when_the_item_count_fits_the_area() {
+        assert_eq!(Menu::scrollbar_position(true, 5, 10, Some(2)), None);
+    }
+
+    #[test]
+    fn scrollbar_is_suppressed_when_disabled_via_args() {
+        assert_eq!(Menu::scrollbar_position(false, 50, 10, Some(23)), None);
+    }
+
+    #[test]
+    fn scrollbar_position_tracks_the_current_index_for_an_overflowing_menu() {
+        let items: Vec<String> = (0..50).map(|i| i.to_string()).collect();
+        let mut menu = Menu::new(items, MenuArgs::default());
+        menu.select(Some(23));
+        let position = Menu::scrollbar_position(menu.args.scrollbar, menu.selectable.items.len(), 10, menu.current_index());
+        assert_eq!(position, Some(23));
+    }
+}
@@ -0,0 +1,508 @@
+//! A `Menu` with a `/`-triggered text filter, for pickers whose item list is
+//! too long to scan by eye (filesystems, locales, timezones, ...).
+
+use std::collections::HashSet;
+
+use crossterm::event::{Event, KeyCode, KeyEventKind, KeyModifiers, MouseEvent};
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph},
+    Frame,
+};
+
+use crate::extensions::StrExt;
+use crate::tui::widgets::input::{Input, InputArgs, InputCommand};
+use crate::tui::widgets::menu::{Menu, MenuArgs};
+use crate::tui::widgets::selectable::SelectableWidget;
+
+/// How `SearchableMenu` folds case while fuzzy-matching, toggled with
+/// Ctrl+S while searching.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+enum SearchCase {
+    /// Always fold case — "GB" matches "gb". Today's default.
+    #[default]
+    Insensitive,
+    /// Fold case only if the query is all-lowercase, like `vim`/`ripgrep`'s
+    /// smart-case: typing an uppercase letter narrows the search to an
+    /// exact-case match.
+    Smart,
+}
+
+impl SearchCase {
+    fn label(self) -> &'static str {
+        match self {
+            SearchCase::Insensitive => "case-insensitive",
+            SearchCase::Smart => "smart-case",
+        }
+    }
+
+    fn toggled(self) -> Self {
+        match self {
+            SearchCase::Insensitive => SearchCase::Smart,
+            SearchCase::Smart => SearchCase::Insensitive,
+        }
+    }
+}
+
+/// A single-select list with a `/`-triggered fuzzy filter over its items.
+pub struct SearchableMenu {
+    title: String,
+    all_items: Vec<String>,
+    filtered: SelectableWidget<String>,
+    /// Maps a position in `filtered` back to its index in `all_items`, so
+    /// selection and `current_item()`-adjacent lookups stay correct no
+    /// matter how the search has reordered or narrowed the visible list.
+    filtered_indices: Vec<usize>,
+    search: Input,
+    searching: bool,
+    /// Persists across searches (surviving `/` open/close) so a user who
+    /// picks smart-case once doesn't have to redo it on every menu.
+    search_case: SearchCase,
+    scrollbar: bool,
+    multi_select: bool,
+    /// Indices into `all_items` toggled on with `Space` when `multi_select`
+    /// is set. Keyed by original index (not `filtered` position) so a
+    /// selection made before or during a search still means the same item
+    /// once the query changes or is cleared.
+    selected: HashSet<usize>,
+    /// The `Rect` the item list (not the search box) was last rendered
+    /// into, so `handle_mouse` can hit-test a click without the caller
+    /// having to hand the area back in. `None` until the first `render`.
+    last_list_area: Option<Rect>,
+}
+
+impl SearchableMenu {
+    pub fn new(items: Vec<String>, args: MenuArgs) -> Self {
+        let mut filtered = SelectableWidget::new(items.clone());
+        filtered.set_wrap(args.wrap);
+        filtered.select_first_item();
+        Self {
+            title: args.title,
+            scrollbar: args.scrollbar,
+            multi_select: args.multi_select,
+            filtered_indices: (0..items.len()).collect(),
+            filtered,
+            all_items: items,
+            last_list_area: None,
+            search: Input::new(InputArgs::default().title("Search".into())),
+            searching: false,
+            search_case: SearchCase::default(),
+            selected: HashSet::new(),
+        }
+    }
+
+    /// Toggles multi-select membership for whatever `filtered` currently has
+    /// highlighted, resolved back to its `all_items` index via
+    /// `filtered_indices` so it's recorded correctly even mid-search.
+    fn toggle_current(&mut self) {
+        let Some(pos) = self.filtered.current_index() else { return };
+        let Some(&original) = self.filtered_indices.get(pos) else { return };
+        if !self.selected.remove(&original) {
+            self.selected.insert(original);
+        }
+    }
+
+    /// The multi-selected items, in `all_items` order — stable regardless of
+    /// the order they were toggled in or what the search query currently
+    /// narrows the list to.
+    pub fn selected_items(&self) -> Vec<&String> {
+        let mut indices: Vec<usize> = self.selected.iter().copied().collect();
+        indices.sort_unstable();
+        indices.into_iter().map(|i| &self.all_items[i]).collect()
+    }
+
+    /// `filtered.items`, each prefixed with a `[x]`/`[ ]` checkbox marker
+    /// when `multi_select` is on — mirrors the checkbox style `Main` already
+    /// uses for its own entries. Left untouched (no prefix) when
+    /// multi-select is off.
+    fn display_items(&self) -> Vec<String> {
+        if !self.multi_select {
+            return self.filtered.items.clone();
+        }
+        self.filtered
+            .items
+            .iter()
+            .zip(&self.filtered_indices)
+            .map(|(item, original)| {
+                let marker = if self.selected.contains(original) { "[x] " } else { "[ ] " };
+                format!("{marker}{item}")
+            })
+            .collect()
+    }
+
+    pub fn current_item(&self) -> Option<&String> {
+        self.filtered.current_item()
+    }
+
+    pub fn current_index(&self) -> Option<usize> {
+        self.filtered.current_index()
+    }
+
+    /// Selects `index` into `filtered` directly, e.g. to seed a freshly
+    /// built menu with an existing value highlighted instead of the first
+    /// item it starts on.
+    pub fn select(&mut self, index: Option<usize>) {
+        self.filtered.select(index);
+    }
+
+    /// Whether `/` has put this menu into search-entry mode. Each
+    /// `SearchableMenu` owns this flag itself (there's no shared/global
+    /// "who's searching" state), so in a multi-pane view only the instance a
+    /// key event is actually dispatched to can ever flip it — a caller
+    /// forwarding `/` to every pane's menu would still only affect the
+    /// focused one.
+    pub fn is_searching(&self) -> bool {
+        self.searching
+    }
+
+    /// One-line description of the active case-matching mode, shown next to
+    /// the item list while searching so the user can see which one's on.
+    pub fn search_hint(&self) -> &'static str {
+        self.search_case.label()
+    }
+
+    /// Whether `query` should be matched with exact case under the current
+    /// `search_case` mode: never for `Insensitive`, only once `query`
+    /// itself contains an uppercase letter for `Smart`.
+    fn is_case_sensitive(&self, query: &str) -> bool {
+        match self.search_case {
+            SearchCase::Insensitive => false,
+            SearchCase::Smart => query.chars().any(|c| c.is_uppercase()),
+        }
+    }
+
+    /// Rebuilds `filtered` from `all_items` against the current search
+    /// query: a fuzzy subsequence match (see `StrExt::fuzzy_match`), sorted
+    /// by descending score so the closest match sits at the top instead of
+    /// wherever it happened to fall in `all_items`. `current_index()` reads
+    /// straight from `filtered`, so re-sorting it doesn't need any separate
+    /// index bookkeeping — the current selection is always "position N in
+    /// whatever `filtered` currently holds".
+    fn refilter(&mut self) {
+        let query = self.search.as_str();
+        let case_sensitive = self.is_case_sensitive(query);
+        let matches: Vec<(usize, String)> = if query.is_empty() {
+            self.all_items.iter().cloned().enumerate().collect()
+        } else {
+            let mut scored: Vec<(i64, usize, &String)> = self
+                .all_items
+                .iter()
+                .enumerate()
+                .filter_map(|(i, item)| {
+                    item.fuzzy_match_with_case(query, case_sensitive).map(|(score, _)| (score, i, item))
+                })
+                .collect();
+            scored.sort_by(|a, b| b.0.cmp(&a.0));
+            scored.into_iter().map(|(_, i, item)| (i, item.clone())).collect()
+        };
+        self.filtered_indices = matches.iter().map(|(i, _)| *i).collect();
+        self.filtered.update_state(matches.into_iter().map(|(_, item)| item).collect());
+        if self.filtered.current_index().is_none() && !self.filtered.items.is_empty() {
+            self.filtered.select_first_item();
+        }
+    }
+
+    /// Feeds a key event to the menu. Per the focus-dispatch contract on
+    /// `View`, while searching this consumes every key itself (letters go to
+    /// the query, not navigation). Returns `Some(true)` if Enter selected an
+    /// item, `Some(false)` if Esc closed the menu outright (only possible
+    /// while not searching — Esc while searching just exits search mode).
+    pub fn on_event(&mut self, event: &Event) -> Option<bool> {
+        if let Event::Mouse(mouse) = event {
+            self.handle_mouse(mouse);
+            return None;
+        }
+        let Event::Key(key) = event else { return None };
+        if key.kind != KeyEventKind::Press {
+            return None;
+        }
+
+        if self.searching {
+            match key.code {
+                KeyCode::Up => {
+                    self.filtered.select_prev_item();
+                    None
+                }
+                KeyCode::Down => {
+                    self.filtered.select_next_item();
+                    None
+                }
+                KeyCode::Char('s') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                    self.search_case = self.search_case.toggled();
+                    self.refilter();
+                    None
+                }
+                KeyCode::Char(' ') if self.multi_select => {
+                    self.toggle_current();
+                    None
+                }
+                _ => match self.search.on_event(event) {
+                    Some(InputCommand::Submit) => Some(true),
+                    Some(InputCommand::Cancel | InputCommand::Empty) => {
+                        self.search.take();
+                        self.searching = false;
+                        self.refilter();
+                        None
+                    }
+                    None => {
+                        self.refilter();
+                        None
+                    }
+                },
+            }
+        } else {
+            match key.code {
+                KeyCode::Char('/') => {
+                    self.searching = true;
+                    None
+                }
+                KeyCode::Down | KeyCode::Char('j') => {
+                    self.filtered.select_next_item();
+                    None
+                }
+                KeyCode::Up | KeyCode::Char('k') => {
+                    self.filtered.select_prev_item();
+                    None
+                }
+                KeyCode::Char(' ') if self.multi_select => {
+                    self.toggle_current();
+                    None
+                }
+                KeyCode::Enter => Some(true),
+                KeyCode::Esc => Some(false),
+                _ => None,
+            }
+        }
+    }
+
+    /// Hit-tests a mouse event against the list area this menu was last
+    /// rendered into (never the search box, which has no meaningful click
+    /// target of its own). A no-op before the first render.
+    pub fn handle_mouse(&mut self, event: &MouseEvent) -> bool {
+        match self.last_list_area {
+            Some(area) => self.filtered.handle_mouse(area, event),
+            None => false,
+        }
+    }
+
+    pub fn render(&mut self, frame: &mut Frame, area: Rect) {
+        let display_items = self.display_items();
+        if self.searching {
+            let chunks = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Length(3), Constraint::Min(0)])
+                .split(area);
+            self.search.render(frame, chunks[0]);
+            self.last_list_area = Some(chunks[1]);
+            let title = format!("{} ({})", self.title, self.search_hint());
+            if self.filtered.items.is_empty() {
+                Self::render_no_matches(frame, chunks[1], &title, self.search.as_str());
+            } else {
+                Menu::render_from_iter(
+                    frame,
+                    chunks[1],
+                    &title,
+                    display_items.iter().map(String::as_str),
+                    &mut self.filtered,
+                    ratatui::style::Style::default(),
+                    self.scrollbar,
+                );
+            }
+        } else {
+            self.last_list_area = Some(area);
+            Menu::render_from_iter(
+                frame,
+                area,
+                &self.title,
+                display_items.iter().map(String::as_str),
+                &mut self.filtered,
+                ratatui::style::Style::default(),
+                self.scrollbar,
+            );
+        }
+    }
+
+    /// Drawn in place of the (empty) list when a search matches nothing, so
+    /// the user sees why the menu went blank instead of it looking broken.
+    /// Navigation keys still route to `filtered` as usual — with nothing in
+    /// it, `SelectableWidget`'s empty-list guards already make them no-ops.
+    fn render_no_matches(frame: &mut Frame, area: Rect, title: &str, query: &str) {
+        let block = Block::default().borders(Borders::ALL).title(title.to_string());
+        let message = format!("No matches for '{query}'");
+        let paragraph = Paragraph::new(Line::from(Span::styled(message, Style::default().fg(Color::DarkGray))))
+            .alignment(Alignment::Center)
+            .block(block);
+        frame.render_widget(paragraph, area);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::event::{KeyEvent, KeyModifiers, MouseButton, MouseEventKind};
+
+    use super::*;
+
+    fn key(code: KeyCode) -> Event {
+        Event::Key(KeyEvent::new(code, KeyModifiers::NONE))
+    }
+
+    fn menu() -> SearchableMenu {
+        SearchableMenu::new(vec!["alpha".into(), "beta".into()], MenuArgs::default())
+    }
+
+    fn ctrl_key(code: KeyCode) -> Event {
+        Event::Key(KeyEvent::new(code, KeyModifiers::CONTROL))
+    }
+
+    #[test]
+    fn search_is_case_insensitive_by_default() {
+        let mut menu = SearchableMenu::new(vec!["Locale".into(), "locale".into()], MenuArgs::default());
+        menu.on_event(&key(KeyCode::Char('/')));
+        menu.on_event(&key(KeyCode::Char('L')));
+        assert_eq!(menu.filtered.items.len(), 2);
+        assert_eq!(menu.search_hint(), "case-insensitive");
+    }
+
+    #[test]
+    fn ctrl_s_switches_to_smart_case_and_the_hint_reflects_it() {
+        let mut menu = SearchableMenu::new(vec!["Locale".into(), "locale".into()], MenuArgs::default());
+        menu.on_event(&key(KeyCode::Char('/')));
+        menu.on_event(&ctrl_key(KeyCode::Char('s')));
+        assert_eq!(menu.search_hint(), "smart-case");
+        menu.on_event(&key(KeyCode::Char('L')));
+        assert_eq!(menu.filtered.items, vec!["Locale".to_string()]);
+    }
+
+    #[test]
+    fn smart_case_still_folds_case_for_an_all_lowercase_query() {
+        let mut menu = SearchableMenu::new(vec!["Locale".into(), "locale".into()], MenuArgs::default());
+        menu.on_event(&key(KeyCode::Char('/')));
+        menu.on_event(&ctrl_key(KeyCode::Char('s')));
+        menu.on_event(&key(KeyCode::Char('l')));
+        assert_eq!(menu.filtered.items.len(), 2);
+    }
+
+    #[test]
+    fn search_case_persists_across_closing_and_reopening_search() {
+        let mut menu = SearchableMenu::new(vec!["Locale".into(), "locale".into()], MenuArgs::default());
+        menu.on_event(&key(KeyCode::Char('/')));
+        menu.on_event(&ctrl_key(KeyCode::Char('s')));
+        menu.on_event(&key(KeyCode::Esc));
+        assert!(!menu.is_searching());
+        menu.on_event(&key(KeyCode::Char('/')));
+        assert_eq!(menu.search_hint(), "smart-case");
+    }
+
+    #[test]
+    fn searching_ranks_the_closest_fuzzy_match_first() {
+        let mut menu = SearchableMenu::new(vec!["abc".into(), "adc".into(), "dxc".into()], MenuArgs::default());
+        menu.on_event(&key(KeyCode::Char('/')));
+        menu.on_event(&key(KeyCode::Char('d')));
+        menu.on_event(&key(KeyCode::Char('c')));
+        assert_eq!(menu.filtered.items, vec!["adc".to_string(), "dxc".to_string()]);
+    }
+
+    #[test]
+    fn space_toggles_selection_when_multi_select_is_enabled() {
+        let mut menu =
+            SearchableMenu::new(vec!["alpha".into(), "beta".into()], MenuArgs::default().multi_select(true));
+        menu.on_event(&key(KeyCode::Char(' ')));
+        assert_eq!(menu.selected_items(), vec![&"alpha".to_string()]);
+        menu.on_event(&key(KeyCode::Char(' ')));
+        assert!(menu.selected_items().is_empty());
+    }
+
+    #[test]
+    fn space_is_ignored_when_multi_select_is_disabled() {
+        let mut menu = menu();
+        menu.on_event(&key(KeyCode::Char(' ')));
+        assert!(menu.selected_items().is_empty());
+    }
+
+    #[test]
+    fn selection_survives_searching_and_maps_back_to_the_original_items() {
+        let mut menu = SearchableMenu::new(
+            vec!["alpha".into(), "beta".into(), "gamma".into()],
+            MenuArgs::default().multi_select(true),
+        );
+        menu.on_event(&key(KeyCode::Down));
+        menu.on_event(&key(KeyCode::Char(' '))); // select "beta"
+
+        menu.on_event(&key(KeyCode::Char('/')));
+        menu.on_event(&key(KeyCode::Char('g')));
+        menu.on_event(&key(KeyCode::Char(' '))); // select "gamma" while filtered to just it
+        menu.on_event(&key(KeyCode::Esc)); // back to the full, unfiltered list
+
+        assert_eq!(menu.selected_items(), vec![&"beta".to_string(), &"gamma".to_string()]);
+    }
+
+    #[test]
+    fn checkbox_prefix_reflects_selection_and_disappears_without_multi_select() {
+        let mut menu =
+            SearchableMenu::new(vec!["alpha".into(), "beta".into()], MenuArgs::default().multi_select(true));
+        menu.on_event(&key(KeyCode::Char(' ')));
+        assert_eq!(menu.display_items(), vec!["[x] alpha".to_string(), "[ ] beta".to_string()]);
+
+        let plain = menu();
+        assert_eq!(plain.display_items(), vec!["alpha".to_string(), "beta".to_string()]);
+    }
+
+    #[test]
+    fn searching_with_no_matches_leaves_filtered_empty_and_navigation_a_no_op() {
+        let mut menu = SearchableMenu::new(vec!["alpha".into(), "beta".into()], MenuArgs::default());
+        menu.on_event(&key(KeyCode::Char('/')));
+        menu.on_event(&key(KeyCode::Char('z')));
+        assert!(menu.filtered.items.is_empty());
+        assert_eq!(menu.current_index(), None);
+
+        menu.on_event(&key(KeyCode::Down));
+        menu.on_event(&key(KeyCode::Up));
+        assert_eq!(menu.current_index(), None);
+    }
+
+    #[test]
+    fn clicking_a_row_selects_it_and_wheel_scrolls() {
+        let mut menu = SearchableMenu::new(vec!["alpha".into(), "beta".into(), "gamma".into()], MenuArgs::default());
+        menu.last_list_area = Some(Rect { x: 0, y: 0, width: 20, height: 6 });
+
+        let clicked = menu.handle_mouse(&MouseEvent {
+            kind: MouseEventKind::Down(MouseButton::Left),
+            column: 2,
+            row: 2, // border (row 0) + "alpha" (row 1) + "beta" (row 2)
+            modifiers: KeyModifiers::NONE,
+        });
+        assert!(clicked);
+        assert_eq!(menu.current_item(), Some(&"beta".to_string()));
+
+        menu.handle_mouse(&MouseEvent { kind: MouseEventKind::ScrollDown, column: 5, row: 2, modifiers: KeyModifiers::NONE });
+        assert_eq!(menu.current_item(), Some(&"gamma".to_string()));
+    }
+
+    #[test]
+    fn a_mouse_event_dispatched_through_on_event_moves_the_selection() {
+        let mut menu = menu();
+        menu.last_list_area = Some(Rect { x: 0, y: 0, width: 20, height: 6 });
+        menu.on_event(&Event::Mouse(MouseEvent {
+            kind: MouseEventKind::ScrollDown,
+            column: 5,
+            row: 2,
+            modifiers: KeyModifiers::NONE,
+        }));
+        assert_eq!(menu.current_item(), Some(&"beta".to_string()));
+    }
+
+    #[test]
+    fn typing_slash_into_one_menu_does_not_start_search_on_a_sibling() {
+        let mut left = menu();
+        let right = menu();
+
+        left.on_event(&key(KeyCode::Char('/')));
+        left.on_event(&key(KeyCode::Char('a')));
+
+        assert!(left.is_searching());
+        assert!(!right.is_searching());
+        assert_eq!(right.current_item(), Some(&"alpha".to_string()));
+    }
+}
@@ -0,0 +1,308 @@
+//! Generic single-selection list state shared by every menu-shaped widget.
+
+use crossterm::event::{MouseButton, MouseEvent, MouseEventKind};
+use ratatui::{layout::Rect, widgets::ListState};
+
+/// Tracks a `ratatui::widgets::ListState` alongside the number of items it was
+/// last built against, so navigation can wrap correctly without every caller
+/// re-deriving `items.len()`.
+#[derive(Debug)]
+pub struct SelectableWidget<T> {
+    pub items: Vec<T>,
+    state: ListState,
+    last_items_len: usize,
+    /// Whether `select_next_item`/`select_prev_item` wrap past the ends of
+    /// the list (last→first, first→last) or clamp at them. On by default —
+    /// changing it is opt-in via `set_wrap`.
+    wrap: bool,
+}
+
+impl<T> Default for SelectableWidget<T> {
+    fn default() -> Self {
+        Self::new(Vec::new())
+    }
+}
+
+impl<T> SelectableWidget<T> {
+    pub fn new(items: Vec<T>) -> Self {
+        let last_items_len = items.len();
+        Self {
+            items,
+            state: ListState::default(),
+            last_items_len,
+            wrap: true,
+        }
+    }
+
+    /// Switches between wrap-around and clamp-at-the-ends navigation. See
+    /// the `wrap` field doc for what each policy does.
+    pub fn set_wrap(&mut self, wrap: bool) {
+        self.wrap = wrap;
+    }
+
+    pub fn list_state(&mut self) -> &mut ListState {
+        &mut self.state
+    }
+
+    pub fn current_index(&self) -> Option<usize> {
+        self.state.selected()
+    }
+
+    pub fn current_item(&self) -> Option<&T> {
+        self.current_index().and_then(|i| self.items.get(i))
+    }
+
+    pub fn select(&mut self, index: Option<usize>) {
+        self.state.select(index);
+    }
+
+    pub fn select_none(&mut self) {
+        self.state.select(None);
+    }
+
+    pub fn select_first_item(&mut self) {
+        if self.last_items_len > 0 {
+            self.state.select(Some(0));
+        }
+    }
+
+    pub fn select_last_item(&mut self) {
+        if self.last_items_len == 0 {
+            return;
+        }
+        self.state.select(Some(self.last_items_len - 1));
+    }
+
+    pub fn select_next_item(&mut self) {
+        if self.last_items_len == 0 {
+            return;
+        }
+        let next = match self.current_index() {
+            Some(i) if i + 1 < self.last_items_len => i + 1,
+            Some(i) if !self.wrap => i,
+            _ => 0,
+        };
+        self.state.select(Some(next));
+    }
+
+    pub fn select_prev_item(&mut self) {
+        if self.last_items_len == 0 {
+            return;
+        }
+        let prev = match self.current_index() {
+            Some(0) if !self.wrap => 0,
+            Some(0) | None => self.last_items_len - 1,
+            Some(i) => i - 1,
+        };
+        self.state.select(Some(prev));
+    }
+
+    /// Type-ahead: jumps the selection to the next item (after the current
+    /// one, wrapping around) whose text starts with `ch`, case-insensitively.
+    /// Distinct from `/` fuzzy search — this is a fast coarse jump over the
+    /// whole list rather than a filter. Returns whether anything matched.
+    ///
+    /// `text` maps an item to the string matched against; callers pass
+    /// something that strips any leading UI decoration (a `"[x] "` checkbox
+    /// prefix, an icon) so the jump lines up with what the user sees.
+    pub fn jump_to_prefix(&mut self, ch: char, text: impl Fn(&T) -> &str) -> bool {
+        if self.last_items_len == 0 {
+            return false;
+        }
+        let ch = ch.to_ascii_lowercase();
+        let start = self.current_index().map_or(0, |i| i + 1);
+        let starts_with = |item: &T| text(item).chars().next().is_some_and(|c| c.to_ascii_lowercase() == ch);
+
+        let after = self.items.iter().skip(start).position(starts_with);
+        let found = match after {
+            Some(offset) => Some(start + offset),
+            None => self.items.iter().take(start).position(starts_with),
+        };
+
+        if let Some(index) = found {
+            self.state.select(Some(index));
+        }
+        found.is_some()
+    }
+
+    /// Hit-tests a mouse event's coordinates against `area` — the widget's
+    /// last rendered rect, border included — and, if it landed inside,
+    /// applies it: a left click selects the row under the cursor and wheel
+    /// up/down step the selection like the equivalent arrow key. Returns
+    /// whether the event landed inside `area` and was consumed, so a click
+    /// or scroll over a sibling pane/widget falls through instead of being
+    /// swallowed here.
+    pub fn handle_mouse(&mut self, area: Rect, event: &MouseEvent) -> bool {
+        let inner_x = area.x + 1;
+        let inner_y = area.y + 1;
+        let inner_width = area.width.saturating_sub(2);
+        let inner_height = area.height.saturating_sub(2);
+        let hit = event.column >= inner_x
+            && event.column < inner_x + inner_width
+            && event.row >= inner_y
+            && event.row < inner_y + inner_height;
+        if !hit {
+            return false;
+        }
+
+        match event.kind {
+            MouseEventKind::ScrollUp => self.select_prev_item(),
+            MouseEventKind::ScrollDown => self.select_next_item(),
+            MouseEventKind::Down(MouseButton::Left) => {
+                let index = (event.row - inner_y) as usize;
+                if index < self.last_items_len {
+                    self.select(Some(index));
+                }
+            }
+            _ => return false,
+        }
+        true
+    }
+
+    /// Replaces the backing items, clamping the current selection so it never
+    /// points past the end of the new list.
+    pub fn update_state(&mut self, items: Vec<T>) {
+        self.last_items_len = items.len();
+        self.items = items;
+        if let Some(i) = self.current_index() {
+            if self.last_items_len == 0 {
+                self.state.select(None);
+            } else if i >= self.last_items_len {
+                self.state.select(Some(self.last_items_len - 1));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn widget() -> SelectableWidget<String> {
+        SelectableWidget::new(vec!["us".into(), "de".into(), "fr".into(), "colemak".into()])
+    }
+
+    #[test]
+    fn jump_selects_the_first_matching_item() {
+        let mut widget = widget();
+        assert!(widget.jump_to_prefix('f', |s| s));
+        assert_eq!(widget.current_index(), Some(2));
+    }
+
+    #[test]
+    fn jump_is_case_insensitive() {
+        let mut widget = widget();
+        assert!(widget.jump_to_prefix('D', |s| s));
+        assert_eq!(widget.current_index(), Some(1));
+    }
+
+    #[test]
+    fn jump_wraps_around_past_the_current_selection() {
+        let mut widget = widget();
+        widget.select(Some(2)); // "fr"
+        // No other item starts with 'f' after index 2, so it should wrap
+        // around and land back on "fr" itself rather than finding nothing.
+        assert!(widget.jump_to_prefix('f', |s| s));
+        assert_eq!(widget.current_index(), Some(2));
+    }
+
+    #[test]
+    fn jump_with_no_match_leaves_selection_untouched() {
+        let mut widget = widget();
+        widget.select(Some(1));
+        assert!(!widget.jump_to_prefix('z', |s| s));
+        assert_eq!(widget.current_index(), Some(1));
+    }
+
+    #[test]
+    fn select_next_item_does_not_panic_after_the_list_shrinks_to_empty() {
+        let mut widget = widget();
+        widget.select(Some(1));
+        widget.update_state(Vec::new());
+        widget.select_next_item();
+        assert_eq!(widget.current_index(), None);
+    }
+
+    #[test]
+    fn select_prev_and_last_item_do_not_panic_on_an_empty_list() {
+        let mut widget: SelectableWidget<String> = SelectableWidget::new(Vec::new());
+        widget.select_prev_item();
+        assert_eq!(widget.current_index(), None);
+        widget.select_last_item();
+        assert_eq!(widget.current_index(), None);
+    }
+
+    fn click_at(column: u16, row: u16) -> MouseEvent {
+        MouseEvent { kind: MouseEventKind::Down(MouseButton::Left), column, row, modifiers: crossterm::event::KeyModifiers::NONE }
+    }
+
+    fn scroll(kind: MouseEventKind) -> MouseEvent {
+        MouseEvent { kind, column: 5, row: 2, modifiers: crossterm::event::KeyModifiers::NONE }
+    }
+
+    #[test]
+    fn a_click_on_a_row_selects_that_index() {
+        let mut widget = widget();
+        let area = Rect { x: 0, y: 0, width: 20, height: 6 };
+        // Row 0 of `area` is the top border, so item 0 sits at row 1.
+        assert!(widget.handle_mouse(area, &click_at(5, 3)));
+        assert_eq!(widget.current_index(), Some(2)); // "fr"
+    }
+
+    #[test]
+    fn a_click_outside_the_area_is_not_consumed() {
+        let mut widget = widget();
+        let area = Rect { x: 0, y: 0, width: 20, height: 6 };
+        assert!(!widget.handle_mouse(area, &click_at(50, 50)));
+        assert_eq!(widget.current_index(), None);
+    }
+
+    #[test]
+    fn a_click_past_the_last_item_but_inside_the_border_is_consumed_but_selects_nothing() {
+        let mut widget = widget(); // 4 items
+        let area = Rect { x: 0, y: 0, width: 20, height: 10 };
+        assert!(widget.handle_mouse(area, &click_at(5, 8)));
+        assert_eq!(widget.current_index(), None);
+    }
+
+    #[test]
+    fn wheel_up_and_down_step_the_selection() {
+        let mut widget = widget();
+        let area = Rect { x: 0, y: 0, width: 20, height: 6 };
+        widget.select(Some(1));
+        assert!(widget.handle_mouse(area, &scroll(MouseEventKind::ScrollDown)));
+        assert_eq!(widget.current_index(), Some(2));
+        assert!(widget.handle_mouse(area, &scroll(MouseEventKind::ScrollUp)));
+        assert_eq!(widget.current_index(), Some(1));
+    }
+
+    #[test]
+    fn wheel_scroll_on_an_empty_list_does_not_panic() {
+        let mut widget: SelectableWidget<String> = SelectableWidget::new(Vec::new());
+        let area = Rect { x: 0, y: 0, width: 20, height: 6 };
+        assert!(widget.handle_mouse(area, &scroll(MouseEventKind::ScrollDown)));
+        assert_eq!(widget.current_index(), None);
+    }
+
+    #[test]
+    fn select_next_and_prev_wrap_around_by_default() {
+        let mut widget = widget(); // 4 items
+        widget.select(Some(3));
+        widget.select_next_item();
+        assert_eq!(widget.current_index(), Some(0));
+        widget.select_prev_item();
+        assert_eq!(widget.current_index(), Some(3));
+    }
+
+    #[test]
+    fn select_next_and_prev_clamp_at_the_ends_when_wrap_is_disabled() {
+        let mut widget = widget(); // 4 items
+        widget.set_wrap(false);
+        widget.select(Some(3));
+        widget.select_next_item();
+        assert_eq!(widget.current_index(), Some(3));
+        widget.select(Some(0));
+        widget.select_prev_item();
+        assert_eq!(widget.current_index(), Some(0));
+    }
+}
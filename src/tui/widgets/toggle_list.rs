@@ -0,0 +1,89 @@
+//! A vertical list of labeled boolean options, navigated the same way as
+//! `Menu` but toggling a checkbox per item instead of committing to a single
+//! selection. For screens with several independent yes/no choices (e.g.
+//! optional install steps) where `Menu`'s single-select model doesn't fit.
+
+use ratatui::{
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::Line,
+    widgets::{Block, Borders, List, ListItem},
+    Frame,
+};
+
+use crate::macros::widget_args;
+use crate::tui::widgets::selectable::SelectableWidget;
+
+widget_args! {
+    pub struct ToggleListArgs {
+        title: String = String::new(),
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct ToggleList {
+    pub selectable: SelectableWidget<String>,
+    values: Vec<bool>,
+    args: ToggleListArgs,
+}
+
+impl ToggleList {
+    pub fn new(items: Vec<(String, bool)>, args: ToggleListArgs) -> Self {
+        let (labels, values): (Vec<String>, Vec<bool>) = items.into_iter().unzip();
+        Self { selectable: SelectableWidget::new(labels), values, args }
+    }
+
+    pub fn values(&self) -> &[bool] {
+        &self.values
+    }
+
+    pub fn is_checked(&self, index: usize) -> Option<bool> {
+        self.values.get(index).copied()
+    }
+
+    /// Flips the highlighted item's value. A no-op if nothing's selected.
+    pub fn toggle_selected(&mut self) {
+        if let Some(value) = self.selectable.current_index().and_then(|i| self.values.get_mut(i)) {
+            *value = !*value;
+        }
+    }
+
+    pub fn render(&mut self, frame: &mut Frame, area: Rect) {
+        let list_items: Vec<ListItem> = self
+            .selectable
+            .items
+            .iter()
+            .zip(&self.values)
+            .map(|(label, checked)| {
+                let marker = if *checked { "[x]" } else { "[ ]" };
+                ListItem::new(Line::from(format!("{marker} {label}")))
+            })
+            .collect();
+        let block = Block::default().borders(Borders::ALL).title(self.args.title.clone());
+        let list = List::new(list_items)
+            .block(block)
+            .highlight_style(Style::default().add_modifier(Modifier::BOLD).fg(Color::Cyan))
+            .highlight_symbol("> ");
+        frame.render_stateful_widget(list, area, self.selectable.list_state());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn toggle_flips_only_the_selected_item() {
+        let mut list = ToggleList::new(vec![("a".into(), false), ("b".into(), false)], ToggleListArgs::default());
+        list.selectable.select(Some(1));
+        list.toggle_selected();
+        assert_eq!(list.values(), &[false, true]);
+    }
+
+    #[test]
+    fn toggle_with_nothing_selected_is_a_no_op() {
+        let mut list = ToggleList::new(vec![("a".into(), false)], ToggleListArgs::default());
+        list.toggle_selected();
+        assert_eq!(list.values(), &[false]);
+    }
+}